@@ -1,7 +1,12 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    etc::ErasedError,
+    Solution,
+};
 use std::{error::Error, num::ParseIntError, str::FromStr};
 
-const INPUT: &str = include_str!("../../input/day01.txt");
+pub const DAY: Day = Day::new::<Puzzle>(1, "Calorie Counting");
+
 ///////////////////////////////////////////////////////////////////////////////
 
 struct Elf {
@@ -25,21 +30,34 @@ impl FromStr for Elf {
     }
 }
 
-fn part_1(input: &str) -> u64 {
-    let elfs: Vec<Elf> = input.split("\n\n").map(|s| s.parse().unwrap()).collect();
+fn parse_input(input: &str) -> Result<Vec<Elf>, ErasedError> {
+    input.split("\n\n").map(|s| s.parse()).collect()
+}
+
+fn part_1(elfs: &[Elf]) -> u64 {
     elfs.iter().map(|elf| elf.total_calories()).max().unwrap()
 }
 
-fn part_2(input: &str) -> u64 {
-    let elfs: Vec<Elf> = input.split("\n\n").map(|s| s.parse().unwrap()).collect();
-    let mut calories: Vec<u64> = elfs.into_iter().map(|elf| elf.total_calories()).collect();
+fn part_2(elfs: &[Elf]) -> u64 {
+    let mut calories: Vec<u64> = elfs.iter().map(|elf| elf.total_calories()).collect();
     calories.sort();
     calories[calories.len() - 3..].iter().sum()
 }
 
-pub fn solve() -> SolutionPair {
-    let sol1: u64 = part_1(&INPUT);
-    let sol2: u64 = part_2(&INPUT);
+struct Puzzle;
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+impl Solver for Puzzle {
+    type Parsed = Vec<Elf>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        parse_input(input)
+    }
+
+    fn part_1(elfs: &Self::Parsed) -> Solution {
+        Solution::U64(part_1(elfs))
+    }
+
+    fn part_2(elfs: &Self::Parsed) -> Solution {
+        Solution::U64(part_2(elfs))
+    }
 }
@@ -1,6 +1,12 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    etc::ErasedError,
+    Solution,
+};
 use std::{error::Error, str::FromStr};
 
+pub const DAY: Day = Day::new::<Puzzle>(2, "Rock Paper Scissors");
+
 ///////////////////////////////////////////////////////////////////////////////
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Action {
@@ -130,37 +136,51 @@ impl FromStr for Game {
     }
 }
 
-fn part_1(input: &str) -> u64 {
-    let games: Vec<Game> = input.lines().map(|s| s.parse().unwrap()).collect();
-    games.iter().map(|g| g.play()).sum()
+/// Each line read both ways: the second column as an action for part 1 and as an
+/// outcome for part 2.
+fn parse_input(input: &str) -> Result<Vec<(Game, Game)>, ErasedError> {
+    input
+        .lines()
+        .map(|s| Ok((s.parse()?, Game::from_str_2(s)?)))
+        .collect()
 }
 
-fn part_2(input: &str) -> u64 {
-    let games: Vec<Game> = input
-        .lines()
-        .map(|s| Game::from_str_2(s).unwrap())
-        .collect();
-    games.iter().map(|g| g.play()).sum()
+fn part_1(games: &[(Game, Game)]) -> u64 {
+    games.iter().map(|(g, _)| g.play()).sum()
 }
 
-pub fn solve() -> SolutionPair {
-    const INPUT: &str = include_str!("../../input/day02.txt");
-    let sol1: u64 = part_1(&INPUT);
-    let sol2: u64 = part_2(&INPUT);
+fn part_2(games: &[(Game, Game)]) -> u64 {
+    games.iter().map(|(_, g)| g.play()).sum()
+}
+
+struct Puzzle;
+
+impl Solver for Puzzle {
+    type Parsed = Vec<(Game, Game)>;
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        parse_input(input)
+    }
+
+    fn part_1(games: &Self::Parsed) -> Solution {
+        Solution::U64(part_1(games))
+    }
+
+    fn part_2(games: &Self::Parsed) -> Solution {
+        Solution::U64(part_2(games))
+    }
 }
 
 #[test]
 fn test_part_1() {
     let input = "A Y\nB X\nC Z";
-    let sol = part_1(input);
+    let sol = part_1(&parse_input(input).unwrap());
     assert_eq!(sol, 15)
 }
 
 #[test]
 fn test_part_2() {
     let input = "A Y\nB X\nC Z";
-    let sol = part_2(input);
+    let sol = part_2(&parse_input(input).unwrap());
     assert_eq!(sol, 12)
 }
@@ -1,4 +1,10 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    etc::ErasedError,
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(3, "Rucksack Reorganization");
 
 ///////////////////////////////////////////////////////////////////////////////
 fn priority(c: char) -> u64 {
@@ -24,28 +30,42 @@ fn score_shares<const N: usize>(input: [&str; N]) -> u64 {
     all_present.trailing_zeros() as u64
 }
 
-fn part_1(input: &str) -> u64 {
+fn parse_input(input: &str) -> Vec<String> {
+    input.trim().lines().map(String::from).collect()
+}
+
+fn part_1(rucksacks: &[String]) -> u64 {
     let mut out = 0;
-    for line in input.lines() {
+    for line in rucksacks {
         out += score_shares([&line[..line.len() / 2], &line[line.len() / 2..]])
     }
     out
 }
 
-fn part_2(input: &str) -> u64 {
+fn part_2(rucksacks: &[String]) -> u64 {
     let mut out = 0;
-    for arr in input.lines().array_chunks::<3>() {
+    for arr in rucksacks.iter().map(String::as_str).array_chunks::<3>() {
         out += score_shares(arr)
     }
     out
 }
 
-pub fn solve() -> SolutionPair {
-    const INPUT: &str = include_str!("../../input/day03.txt");
-    let sol1: u64 = part_1(&INPUT.trim());
-    let sol2: u64 = part_2(&INPUT.trim());
+struct Puzzle;
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+impl Solver for Puzzle {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        Ok(parse_input(input))
+    }
+
+    fn part_1(rucksacks: &Self::Parsed) -> Solution {
+        Solution::U64(part_1(rucksacks))
+    }
+
+    fn part_2(rucksacks: &Self::Parsed) -> Solution {
+        Solution::U64(part_2(rucksacks))
+    }
 }
 
 #[test]
@@ -72,13 +92,13 @@ fn test_priority() {
 #[test]
 fn test_part_1() {
     let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
-    let sol = part_1(input);
+    let sol = part_1(&parse_input(input));
     assert_eq!(sol, 157)
 }
 
 #[test]
 fn test_part_2() {
     let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
-    let sol = part_2(input);
+    let sol = part_2(&parse_input(input));
     assert_eq!(sol, 70)
 }
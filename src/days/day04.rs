@@ -1,6 +1,11 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    Solution,
+};
 use std::{error::Error, str::FromStr};
 
+pub const DAY: Day = Day::new::<Puzzle>(4, "Camp Cleanup");
+
 ///////////////////////////////////////////////////////////////////////////////
 type ErasedError = Box<dyn Error + Send + Sync + 'static>;
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -71,17 +76,19 @@ where
     }
 }
 
-fn prepare_input(input: &str) -> Vec<(Interval<i64>, Interval<i64>)> {
+/// The assignments of a pair of elves.
+type Pair<T> = (Interval<T>, Interval<T>);
+
+fn prepare_input(input: &str) -> Result<Vec<Pair<i64>>, ErasedError> {
     let n_lines = input.lines().count();
-    let mut intervals = Vec::<(Interval<i64>, Interval<i64>)>::with_capacity(n_lines);
+    let mut intervals = Vec::<Pair<i64>>::with_capacity(n_lines);
     for line in input.lines() {
-        let mut parts = line.split(",");
-        intervals.push((
-            parts.next().unwrap().parse().unwrap(),
-            parts.next().unwrap().parse().unwrap(),
-        ))
+        let (fst, snd) = line
+            .split_once(',')
+            .ok_or("ERROR: Expected a pair of intervals.".to_string())?;
+        intervals.push((fst.parse()?, snd.parse()?))
     }
-    intervals
+    Ok(intervals)
 }
 
 fn part_1<T: PartialOrd>(intervals: &Vec<(Interval<T>, Interval<T>)>) -> u64 {
@@ -98,13 +105,22 @@ fn part_2<T: PartialOrd>(intervals: &Vec<(Interval<T>, Interval<T>)>) -> u64 {
         .sum()
 }
 
-pub fn solve() -> SolutionPair {
-    const INPUT: &str = include_str!("../../input/day04.txt");
-    let intervals = prepare_input(&INPUT);
-    let sol1: u64 = part_1(&intervals);
-    let sol2: u64 = part_2(&intervals);
+struct Puzzle;
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+impl Solver for Puzzle {
+    type Parsed = Vec<Pair<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        prepare_input(input)
+    }
+
+    fn part_1(intervals: &Self::Parsed) -> Solution {
+        Solution::U64(part_1(intervals))
+    }
+
+    fn part_2(intervals: &Self::Parsed) -> Solution {
+        Solution::U64(part_2(intervals))
+    }
 }
 
 #[test]
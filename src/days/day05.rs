@@ -1,6 +1,12 @@
-use crate::{etc::ErasedError, Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    etc::ErasedError,
+    Solution,
+};
 use std::str::FromStr;
 
+pub const DAY: Day = Day::new::<Puzzle>(5, "Supply Stacks");
+
 ///////////////////////////////////////////////////////////////////////////////
 #[derive(Debug, Clone, PartialEq)]
 struct Move {
@@ -100,28 +106,28 @@ impl<const N: usize> FromStr for Stacks<N> {
     }
 }
 
-fn part_1<const N: usize>(input: &str) -> String {
-    let (stacks_input, moves_input) = input.split_once("\n\n").unwrap();
-    let mut stacks: Stacks<N> = stacks_input.parse().unwrap();
+fn parse_input<const N: usize>(input: &str) -> Result<(Stacks<N>, Vec<Move>), ErasedError> {
+    let (stacks_input, moves_input) = input
+        .split_once("\n\n")
+        .ok_or("ERROR: Expected stacks and moves separated by an empty line.".to_string())?;
+    let stacks: Stacks<N> = stacks_input.parse()?;
     let moves: Vec<Move> = moves_input
         .lines()
-        .map(|line| line.parse().unwrap())
-        .collect();
+        .map(|line| line.parse())
+        .collect::<Result<_, _>>()?;
+    Ok((stacks, moves))
+}
 
+fn part_1<const N: usize>(stacks: &Stacks<N>, moves: &[Move]) -> String {
+    let mut stacks = stacks.clone();
     for move_ in moves {
         move_.apply(&mut stacks).expect("ERROR: Move failed.");
     }
 
     return stacks.get_message();
 }
-fn part_2<const N: usize>(input: &str) -> String {
-    let (stacks_input, moves_input) = input.split_once("\n\n").unwrap();
-    let mut stacks: Stacks<N> = stacks_input.parse().unwrap();
-    let moves: Vec<Move> = moves_input
-        .lines()
-        .map(|line| line.parse().unwrap())
-        .collect();
-
+fn part_2<const N: usize>(stacks: &Stacks<N>, moves: &[Move]) -> String {
+    let mut stacks = stacks.clone();
     for move_ in moves {
         move_.apply_2(&mut stacks).expect("ERROR: Move failed.");
     }
@@ -129,13 +135,22 @@ fn part_2<const N: usize>(input: &str) -> String {
     return stacks.get_message();
 }
 
-pub fn solve() -> SolutionPair {
-    const INPUT: &str = include_str!("../../input/day05.txt");
-    // Your solution here...
-    let sol1: String = part_1::<9>(&INPUT);
-    let sol2: String = part_2::<9>(&INPUT);
+struct Puzzle;
+
+impl Solver for Puzzle {
+    type Parsed = (Stacks<9>, Vec<Move>);
 
-    (Solution::Str(sol1), Solution::Str(sol2))
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        parse_input(input)
+    }
+
+    fn part_1((stacks, moves): &Self::Parsed) -> Solution {
+        Solution::Str(part_1(stacks, moves))
+    }
+
+    fn part_2((stacks, moves): &Self::Parsed) -> Solution {
+        Solution::Str(part_2(stacks, moves))
+    }
 }
 
 #[test]
@@ -155,6 +170,7 @@ fn test_parse_stacks() {
 #[test]
 fn test_part_1() {
     const INPUT: &str = include_str!("../../input/day05_test.txt");
-    let res = part_1::<3>(&INPUT);
+    let (stacks, moves) = parse_input::<3>(INPUT).unwrap();
+    let res = part_1(&stacks, &moves);
     println!("{}", res);
 }
@@ -1,4 +1,10 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    etc::ErasedError,
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(6, "Tuning Trouble");
 
 ///////////////////////////////////////////////////////////////////////////////
 #[derive(Debug)]
@@ -42,13 +48,22 @@ fn find_packet_start<const WINDOW: usize>(input: &str) -> u64 {
     0
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
-    const INPUT: &str = include_str!("../../input/day06.txt");
-    let sol1: u64 = find_packet_start::<4>(INPUT);
-    let sol2: u64 = find_packet_start::<14>(INPUT);
+struct Puzzle;
+
+impl Solver for Puzzle {
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        Ok(input.trim().to_string())
+    }
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+    fn part_1(signal: &Self::Parsed) -> Solution {
+        Solution::U64(find_packet_start::<4>(signal))
+    }
+
+    fn part_2(signal: &Self::Parsed) -> Solution {
+        Solution::U64(find_packet_start::<14>(signal))
+    }
 }
 
 #[test]
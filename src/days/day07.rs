@@ -1,6 +1,12 @@
 use std::{str::FromStr, vec};
 
-use crate::{etc::ErasedError, Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    etc::ErasedError,
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(7, "No Space Left On Device");
 
 ///////////////////////////////////////////////////////////////////////////////
 #[derive(Debug, Clone, PartialEq)]
@@ -243,13 +249,22 @@ fn part_2(node_buffer: &Vec<Node>) -> u64 {
     ret_val
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day07.txt");
-    let node_buffer = parse_input(input).expect("ERROR: Could not parse input.");
-    let sol1: u64 = part_1(&node_buffer);
-    let sol2: u64 = part_2(&node_buffer);
+struct Puzzle;
+
+impl Solver for Puzzle {
+    type Parsed = Vec<Node>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        parse_input(input)
+    }
+
+    fn part_1(node_buffer: &Self::Parsed) -> Solution {
+        Solution::U64(part_1(node_buffer))
+    }
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+    fn part_2(node_buffer: &Self::Parsed) -> Solution {
+        Solution::U64(part_2(node_buffer))
+    }
 }
 
 #[test]
@@ -1,223 +1,195 @@
 use std::{fmt::Display, str::FromStr};
 
-use crate::{etc::ErasedError, Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver, Visualize},
+    etc::{
+        grid::{parse_grid, Cells, Direction, View},
+        ErasedError, Grid,
+    },
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(8, "Treetop Tree House").visualized::<Puzzle>();
 
 ///////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Woods<const N: usize> {
-    trees: [[i8; N]; N],
+struct Woods {
+    trees: Grid<i8>,
 }
 
-impl<const N: usize> Woods<N> {
-    fn visible_left(&self) -> [[bool; N]; N] {
-        let mut visible = [[true; N]; N];
-        for i in 0..N {
+impl Woods {
+    /// Marks the trees in `view` that can be seen from its left edge. `visible` is indexed
+    /// like the woods, not like the view.
+    fn mark_visible_from_left(view: View<'_, Grid<i8>>, visible: &mut Grid<bool>) {
+        for i in 0..view.rows() {
             let mut max_height_so_far = -1;
-            for j in 0..N {
-                let tree = self.trees[i][j];
-                if tree <= max_height_so_far {
-                    visible[i][j] = false
+            for j in 0..view.cols() {
+                let tree = view[[i, j]];
+                if tree > max_height_so_far {
+                    visible[view.source([i, j])] = true
                 }
                 max_height_so_far = max_height_so_far.max(tree)
             }
         }
-        visible
     }
-    fn visible_right(&self) -> [[bool; N]; N] {
-        let mut visible = [[true; N]; N];
-        for i in 0..N {
-            let mut max_height_so_far = -1;
-            for j in 0..N {
-                let tree = self.trees[i][N - j - 1];
-                if tree <= max_height_so_far {
-                    visible[i][N - j - 1] = false
-                }
-                max_height_so_far = max_height_so_far.max(tree)
-            }
-        }
-        visible
+
+    /// The woods turned so that the left edge is the left, right, top and bottom edge in turn.
+    fn sides(&self) -> [View<'_, Grid<i8>>; 4] {
+        let trees = self.trees.view();
+        [
+            trees,
+            trees.flipped_cols(),
+            trees.transposed(),
+            trees.rotated_right(),
+        ]
     }
-    fn visible_top(&self) -> [[bool; N]; N] {
-        let mut visible = [[true; N]; N];
-        for i in 0..N {
-            let mut max_height_so_far = -1;
-            for j in 0..N {
-                let tree = self.trees[j][i];
-                if tree <= max_height_so_far {
-                    visible[j][i] = false
-                }
-                max_height_so_far = max_height_so_far.max(tree)
-            }
+
+    /// Trees visible from any edge.
+    fn visible(&self) -> Grid<bool> {
+        let mut visible = Grid::new(self.trees.rows(), self.trees.cols(), false);
+        for view in self.sides() {
+            Self::mark_visible_from_left(view, &mut visible)
         }
         visible
     }
-    fn visible_bottom(&self) -> [[bool; N]; N] {
-        let mut visible = [[true; N]; N];
-        for i in 0..N {
-            let mut max_height_so_far = -1;
-            for j in 0..N {
-                let tree = self.trees[N - j - 1][i];
-                if tree <= max_height_so_far {
-                    visible[N - j - 1][i] = false
+
+    /// The tree heights, on green where `visible` and on blue elsewhere.
+    fn paint(&self, visible: &Grid<bool>) -> String {
+        const ESCAPE: char = 27 as char;
+        let (m, n) = (self.trees.rows(), self.trees.cols());
+        let mut out: String = "".into();
+        for i in 0..m {
+            for j in 0..n {
+                if visible[[i, j]] {
+                    out.push_str(&format!("{ESCAPE}[42m"));
+                } else {
+                    out.push_str(&format!("{ESCAPE}[44m"));
                 }
-                max_height_so_far = max_height_so_far.max(tree)
+                out.push((self.trees[[i, j]] as u8 + b'0') as char);
+                out.push_str(&format!("{ESCAPE}[0m"));
             }
-        }
-        visible
-    }
-
-    fn visible(&self) -> [[bool; N]; N] {
-        let mut visible = [[false; N]; N];
-        let vl = self.visible_left();
-        let vr = self.visible_right();
-        let vt = self.visible_top();
-        let vb = self.visible_bottom();
-        for i in 0..N {
-            for j in 0..N {
-                visible[i][j] = vl[i][j] || vr[i][j] || vt[i][j] || vb[i][j]
+            if i + 1 < m {
+                out.push('\n')
             }
         }
-        visible
+        out
     }
 
+    /// Product of the number of trees seen in each direction, up to the first one at least as
+    /// high as the tree at `(i, j)`.
     fn scenic_score(&self, i: usize, j: usize) -> u64 {
-        let tree_height = self.trees[i][j];
-        // looking north
-        let mut k = 1;
-        let mut visible_up = 0;
-        while i as isize - k as isize >= 0 {
-            k += 1;
-            if self.trees[i + 1 - k][j] >= tree_height {
-                visible_up += 1;
-                break;
-            }
-            visible_up += 1;
-        }
-        // looking west
-        let mut k = 1;
-        let mut visible_left = 0;
-        while j as isize - k as isize >= 0 {
-            k += 1;
-            if self.trees[i][j + 1 - k] >= tree_height {
-                visible_left += 1;
-                break;
-            }
-            visible_left += 1;
-        }
-        // looking east
-        let mut k = 1;
-        let mut visible_right = 0;
-        while j + k < N {
-            k += 1;
-            if self.trees[i][j + k - 1] >= tree_height {
-                visible_right += 1;
-                break;
-            }
-            visible_right += 1;
-        }
-        // looking down
-        let mut k = 1;
-        let mut visible_down = 0;
-        while i + k < N {
-            k += 1;
-            if self.trees[i + k - 1][j] >= tree_height {
-                visible_down += 1;
-                break;
-            }
-            visible_down += 1;
-        }
-        visible_up * visible_left * visible_right * visible_down
+        let tree_height = self.trees[[i, j]];
+        Direction::ALL
+            .into_iter()
+            .map(|direction| {
+                let mut seen = 0;
+                for idx in self.trees.bounds().ray([i, j], direction) {
+                    seen += 1;
+                    if self.trees[idx] >= tree_height {
+                        break;
+                    }
+                }
+                seen
+            })
+            .product()
     }
 }
 
-impl<const N: usize> FromStr for Woods<N> {
+impl FromStr for Woods {
     type Err = ErasedError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut trees = [[0i8; N]; N];
-        for (i, line) in s.trim().lines().enumerate() {
-            for (j, val) in line.as_bytes().iter().enumerate() {
-                trees[i][j] = (val - '0' as u8) as i8
-            }
-        }
+        let trees = parse_grid(s, &[], |c| c.to_digit(10).map(|height| height as i8))?.grid;
         Ok(Woods { trees })
     }
 }
 
-impl<const N: usize> Display for Woods<N> {
+impl Display for Woods {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        const ESCAPE: char = 27 as char;
-        let vs = self.visible();
-        let mut out: String = "".into();
-        for i in 0..N {
-            for j in 0..N {
-                if vs[i][j] {
-                    out.push_str(&format!("{ESCAPE}[42m"));
-                } else {
-                    out.push_str(&format!("{ESCAPE}[44m"));
-                }
-                out.push((self.trees[i][j] as u8 + '0' as u8) as char);
-                out.push_str(&format!("{ESCAPE}[0m"));
-            }
-            if i < N - 1 {
-                out.push('\n')
-            }
-        }
-        writeln!(f, "{}", out)
+        writeln!(f, "{}", self.paint(&self.visible()))
     }
 }
 
-fn part_1<const N: usize>(input: &str) -> u64 {
-    let woods: Woods<N> = input.parse().unwrap();
-    let visible = woods.visible();
-
-    let mut acc = 0;
-    for i in 0..N {
-        for j in 0..N {
-            acc += visible[i][j] as u64
-        }
-    }
-
-    acc
+fn part_1(woods: &Woods) -> u64 {
+    woods.visible().iter().map(|&visible| visible as u64).sum()
 }
 
-fn part_2<const N: usize>(input: &str) -> u64 {
-    let woods: Woods<N> = input.parse().unwrap();
+fn part_2(woods: &Woods) -> u64 {
     let mut sc = 0;
-    for i in 1..N - 1 {
-        for j in 1..N - 1 {
+    for i in 1..woods.trees.rows().saturating_sub(1) {
+        for j in 1..woods.trees.cols().saturating_sub(1) {
             sc = sc.max(woods.scenic_score(i, j))
         }
     }
     sc
 }
 
-pub fn solve() -> SolutionPair {
-    const INPUT: &str = include_str!("../../input/day08.txt");
-    let sol1: u64 = part_1::<99>(INPUT);
-    let sol2: u64 = part_2::<99>(INPUT);
+struct Puzzle;
+
+/// Five frames of the whole woods: no tree marked visible yet, then the trees seen from the
+/// left, right, top and bottom added one side after the other.
+impl Visualize for Puzzle {
+    fn frames(woods: Self::Parsed) -> impl Iterator<Item = String> + 'static {
+        let mut visible = Grid::new(woods.trees.rows(), woods.trees.cols(), false);
+        let mut frames = vec![woods.paint(&visible)];
+        for view in woods.sides() {
+            Woods::mark_visible_from_left(view, &mut visible);
+            frames.push(woods.paint(&visible));
+        }
+        frames.into_iter()
+    }
+}
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+impl Solver for Puzzle {
+    type Parsed = Woods;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        input.parse()
+    }
+
+    fn part_1(woods: &Self::Parsed) -> Solution {
+        Solution::U64(part_1(woods))
+    }
+
+    fn part_2(woods: &Self::Parsed) -> Solution {
+        Solution::U64(part_2(woods))
+    }
 }
 
 #[test]
 fn test_part_1() {
     let input = include_str!("../../input/day08_test.txt");
-    let woods: Woods<5> = input.parse().unwrap();
-    assert_eq!(woods.trees[0], [3, 0, 3, 7, 3,]);
+    let woods: Woods = input.parse().unwrap();
+    assert_eq!(
+        woods.trees.iter().take(5).copied().collect::<Vec<_>>(),
+        [3, 0, 3, 7, 3,]
+    );
 
-    let count_visible = part_1::<5>(input);
+    let count_visible = part_1(&woods);
     assert_eq!(count_visible, 21);
 }
 
 #[test]
 fn test_part_2() {
     let input = include_str!("../../input/day08_test.txt");
-    let woods: Woods<5> = input.parse().unwrap();
-    assert_eq!(woods.trees[0], [3, 0, 3, 7, 3,]);
+    let woods: Woods = input.parse().unwrap();
+    assert_eq!(
+        woods.trees.iter().take(5).copied().collect::<Vec<_>>(),
+        [3, 0, 3, 7, 3,]
+    );
 
     let scenic_score = woods.scenic_score(1, 2);
     assert_eq!(scenic_score, 4);
     let scenic_score = woods.scenic_score(3, 2);
     assert_eq!(scenic_score, 8);
 }
+
+#[test]
+fn test_frames() {
+    let input = include_str!("../../input/day08_test.txt");
+    let woods: Woods = input.parse().unwrap();
+    let frames: Vec<String> = Puzzle::frames(woods.clone()).collect();
+    assert_eq!(frames.len(), 5);
+    assert!(!frames[0].contains("[42m"));
+    assert_eq!(format!("{}\n", frames[4]), woods.to_string());
+}
@@ -1,6 +1,15 @@
-use std::{collections::HashSet, str::FromStr};
+use std::str::FromStr;
 
-use crate::{etc::ErasedError, Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver, Visualize},
+    etc::{
+        grid::{sparse::step, Direction, Point, SparseGrid},
+        ErasedError,
+    },
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(9, "Rope Bridge").visualized::<Puzzle>();
 
 ///////////////////////////////////////////////////////////////////////////////
 
@@ -21,6 +30,15 @@ impl Move {
             Move::Right(n) => *n,
         }
     }
+
+    fn direction(&self) -> Direction {
+        match self {
+            Move::Up(_) => Direction::Up,
+            Move::Down(_) => Direction::Down,
+            Move::Left(_) => Direction::Left,
+            Move::Right(_) => Direction::Right,
+        }
+    }
 }
 
 impl FromStr for Move {
@@ -42,112 +60,161 @@ impl FromStr for Move {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Rope<const N: usize> {
-    knots: [(i64, i64); N],
+    knots: [Point; N],
 }
 
 impl<const N: usize> Rope<N> {
     fn new() -> Self {
-        Rope { knots: [(0, 0); N] }
+        Rope { knots: [[0, 0]; N] }
     }
 
     fn step_one(&mut self, m: Move) {
-        match m {
-            Move::Up(_) => self.knots[0].0 += 1,
-            Move::Down(_) => self.knots[0].0 -= 1,
-            Move::Left(_) => self.knots[0].1 -= 1,
-            Move::Right(_) => self.knots[0].1 += 1,
-        }
+        self.knots[0] = step(self.knots[0], m.direction());
         self.drag_tail();
     }
 
-    fn step(&mut self, m: Move, visited: &mut HashSet<(i64, i64)>) {
+    fn step(&mut self, m: Move, visited: &mut SparseGrid<char>) {
         for _ in 0..m.get_inner() {
             self.step_one(m);
-            visited.insert(self.knots[N - 1]);
+            visited.insert(self.knots[N - 1], '#');
         }
     }
 
     fn drag_tail(&mut self) {
         for i in 1..N {
-            let vdiff = self.knots[i - 1].0 - self.knots[i].0;
-            let hdiff = self.knots[i - 1].1 - self.knots[i].1;
+            let vdiff = self.knots[i - 1][0] - self.knots[i][0];
+            let hdiff = self.knots[i - 1][1] - self.knots[i][1];
 
             if vdiff.abs() > 1 && hdiff.abs() > 0 {
-                self.knots[i].0 += vdiff.signum();
-                self.knots[i].1 += hdiff.signum();
+                self.knots[i][0] += vdiff.signum();
+                self.knots[i][1] += hdiff.signum();
             } else if vdiff.abs() > 0 && hdiff.abs() > 1 {
-                self.knots[i].0 += vdiff.signum();
-                self.knots[i].1 += hdiff.signum();
+                self.knots[i][0] += vdiff.signum();
+                self.knots[i][1] += hdiff.signum();
             } else if vdiff.abs() > 1 {
-                self.knots[i].0 += vdiff.signum()
+                self.knots[i][0] += vdiff.signum()
             } else if hdiff.abs() > 1 {
-                self.knots[i].1 += hdiff.signum()
+                self.knots[i][1] += hdiff.signum()
             }
         }
     }
 }
 
-fn _show_rope<const M: usize, const N: usize, const K: usize>(rope: &Rope<K>) {
-    for i in (0..M).rev() {
-        for j in 0..N {
-            if (i as i64, j as i64) == rope.knots[0] {
-                print!("H")
-            } else if (i as i64, j as i64) == rope.knots[K] {
-                print!("T")
-            } else {
-                print!(".")
-            }
-        }
-        print!("\n")
+/// The knots over the cells the tail visited: `H` for the head, `T` for the tail, the
+/// number of any knot in between, `s` for the start and `#` for the trail.
+fn picture<const K: usize>(rope: &Rope<K>, visited: &SparseGrid<char>) -> SparseGrid<char> {
+    let mut picture = visited.clone();
+    picture.insert([0, 0], 's');
+    for (i, &knot) in rope.knots.iter().enumerate().rev() {
+        let c = match i {
+            0 => 'H',
+            _ if i == K - 1 => 'T',
+            _ => char::from_digit(i as u32, 36).unwrap_or('#'),
+        };
+        picture.insert(knot, c);
     }
+    picture
 }
 
-fn _show_visited<const M: usize, const N: usize>(visited: &HashSet<(i64, i64)>) {
-    for i in (0..M).rev() {
-        for j in 0..N {
-            if visited.contains(&(i as i64, j as i64)) {
-                print!("#")
-            } else {
-                print!(".")
-            }
-        }
-        print!("\n")
-    }
+fn parse_moves(input: &str) -> Result<Vec<Move>, ErasedError> {
+    input.trim().lines().map(|l| l.parse()).collect()
 }
-fn simulate_rope<const K: usize>(input: &str) -> u64 {
-    let mut visited = HashSet::new();
-    let moves: Vec<Move> = input
-        .trim()
-        .lines()
-        .map(|l| l.parse().expect("ERROR: Could not parse move."))
-        .collect();
+
+/// The cells the tail of a rope with `K` knots visits.
+fn tail_visits<const K: usize>(moves: &[Move]) -> SparseGrid<char> {
+    let mut visited = SparseGrid::new();
     let mut rope = Rope::<K>::new();
-    visited.insert(rope.knots[K - 1]);
+    visited.insert(rope.knots[K - 1], '#');
 
-    for m in moves {
+    for &m in moves {
         rope.step(m, &mut visited)
     }
 
-    visited.len() as u64
+    visited
 }
 
-pub fn solve() -> SolutionPair {
-    const INPUT: &str = include_str!("../../input/day09.txt");
-    let sol1: u64 = simulate_rope::<2>(INPUT);
-    let sol2: u64 = simulate_rope::<10>(INPUT);
+fn simulate_rope<const K: usize>(moves: &[Move]) -> u64 {
+    tail_visits::<K>(moves).len() as u64
+}
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+struct Puzzle;
+
+/// The long rope of part 2 moving one step at a time, dragging its trail behind. The moves
+/// are played once up front to find the box around everywhere the rope goes, so every frame
+/// shows that same box.
+impl Visualize for Puzzle {
+    fn frames(moves: Self::Parsed) -> impl Iterator<Item = String> + 'static {
+        let steps: Vec<Move> = moves
+            .into_iter()
+            .flat_map(|m| std::iter::repeat_n(m, m.get_inner().max(0) as usize))
+            .collect();
+
+        let mut rope = Rope::<10>::new();
+        let [mut min, mut max] = [[0, 0]; 2];
+        for &m in &steps {
+            rope.step_one(m);
+            for knot in rope.knots {
+                min = [min[0].min(knot[0]), min[1].min(knot[1])];
+                max = [max[0].max(knot[0]), max[1].max(knot[1])];
+            }
+        }
+        let window = [min, max];
+
+        let mut rope = Rope::<10>::new();
+        let mut visited = SparseGrid::new();
+        visited.insert(rope.knots[9], '#');
+        let first = picture(&rope, &visited).to_string_in(window);
+        std::iter::once(first).chain(steps.into_iter().map(move |m| {
+            rope.step_one(m);
+            visited.insert(rope.knots[9], '#');
+            picture(&rope, &visited).to_string_in(window)
+        }))
+    }
+}
+
+impl Solver for Puzzle {
+    type Parsed = Vec<Move>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        parse_moves(input)
+    }
+
+    fn part_1(moves: &Self::Parsed) -> Solution {
+        Solution::U64(simulate_rope::<2>(moves))
+    }
+
+    fn part_2(moves: &Self::Parsed) -> Solution {
+        Solution::U64(simulate_rope::<10>(moves))
+    }
 }
 
 #[test]
 fn test_part_1() {
     const INPUT: &str = include_str!("../../input/day09_test.txt");
-    let num_visited = simulate_rope::<2>(INPUT);
+    let num_visited = simulate_rope::<2>(&parse_moves(INPUT).unwrap());
     assert_eq!(num_visited, 13);
 }
 #[test]
 fn test_part_2() {
     const INPUT: &str = include_str!("../../input/day09_test.txt");
-    let num_visited = simulate_rope::<10>(INPUT);
+    let num_visited = simulate_rope::<10>(&parse_moves(INPUT).unwrap());
     assert_eq!(num_visited, 1);
 }
+
+#[test]
+fn test_tail_visits() {
+    const INPUT: &str = include_str!("../../input/day09_test.txt");
+    let visited = tail_visits::<2>(&parse_moves(INPUT).unwrap());
+    assert_eq!(visited.to_string(), "..##.\n...##\n.####\n....#\n####.\n");
+}
+
+#[test]
+fn test_frames() {
+    const INPUT: &str = "R 2\nU 1";
+    let frames: Vec<String> = Puzzle::frames(parse_moves(INPUT).unwrap()).collect();
+    // All frames show the box the whole run needs.
+    assert_eq!(
+        frames,
+        ["...\nH..\n", "...\n1H.\n", "...\n21H\n", "..H\n21.\n"]
+    );
+}
@@ -1,6 +1,12 @@
 use std::str::FromStr;
 
-use crate::{etc::ErasedError, Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    etc::{ocr, solution::Pixels, ErasedError},
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(10, "Cathode-Ray Tube");
 
 ///////////////////////////////////////////////////////////////////////////////
 
@@ -19,8 +25,8 @@ impl Instruction {
     }
 }
 
-fn parse_instructions(s: &str) -> Vec<Instruction> {
-    s.lines().map(|l| l.parse().unwrap()).rev().collect()
+fn parse_instructions(s: &str) -> Result<Vec<Instruction>, ErasedError> {
+    s.lines().map(|l| l.parse()).rev().collect()
 }
 
 impl FromStr for Instruction {
@@ -122,36 +128,41 @@ fn part_1(instructions: Vec<Instruction>) -> i64 {
     signal_strength
 }
 
-fn part_2(instructions: Vec<Instruction>) -> String {
+fn part_2(instructions: Vec<Instruction>) -> Pixels {
     const DISPLAY_WIDTH: usize = 40;
     const DISPLAY_HEIGHT: usize = 6;
 
     let cpu = CPU::new(instructions);
-    let mut out = String::with_capacity(DISPLAY_HEIGHT * DISPLAY_WIDTH);
+    let mut screen = Pixels::new(DISPLAY_HEIGHT, DISPLAY_WIDTH);
 
-    for (idx, pos) in cpu.enumerate() {
-        let (_, col) = (idx / DISPLAY_WIDTH, idx % DISPLAY_WIDTH);
+    // The CPU reports the register once more after the last cycle, which is off screen.
+    for (idx, pos) in cpu.enumerate().take(DISPLAY_HEIGHT * DISPLAY_WIDTH) {
+        let (row, col) = (idx / DISPLAY_WIDTH, idx % DISPLAY_WIDTH);
+        screen.set(row, col, (pos - col as i64).abs() < 2);
+    }
+    screen
+}
 
-        if col == 0 {
-            out.push('\n');
-        }
+struct Puzzle;
 
-        if (pos - col as i64).abs() < 2 {
-            out.push('#');
-        } else {
-            out.push('.')
-        }
+impl Solver for Puzzle {
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        parse_instructions(input)
     }
-    out
-}
 
-pub fn solve() -> SolutionPair {
-    const INPUT: &str = include_str!("../../input/day10.txt");
-    let instructions: Vec<Instruction> = parse_instructions(INPUT);
-    let sol1: i64 = part_1(instructions.clone());
-    let sol2: String = part_2(instructions);
+    fn part_1(instructions: &Self::Parsed) -> Solution {
+        Solution::I64(part_1(instructions.clone()))
+    }
 
-    (Solution::I64(sol1), Solution::Str(sol2))
+    fn part_2(instructions: &Self::Parsed) -> Solution {
+        let picture = part_2(instructions.clone());
+        match ocr::read(&picture) {
+            Ok(text) => Solution::Letters { text, picture },
+            Err(_) => Solution::Picture(picture),
+        }
+    }
 }
 
 mod tests {
@@ -188,7 +199,7 @@ mod tests {
     #[test]
     fn test_part_1() {
         let input = include_str!("../../input/day10_test.txt");
-        let instructions = parse_instructions(input);
+        let instructions = parse_instructions(input).unwrap();
         let res = part_1(instructions);
         assert_eq!(res, 13140)
     }
@@ -208,8 +219,16 @@ mod tests {
     #[test]
     fn test_part_2() {
         let input = include_str!("../../input/day10_test.txt");
-        let instructions = parse_instructions(input);
+        let instructions = parse_instructions(input).unwrap();
         let res = part_2(instructions);
-        println!("{}", res);
+        assert_eq!(
+            res.to_string(),
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+        );
     }
 }
@@ -1,6 +1,12 @@
 use std::str::FromStr;
 
-use crate::{etc::ErasedError, Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    etc::ErasedError,
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(11, "Monkey in the Middle");
 
 ///////////////////////////////////////////////////////////////////////////////
 
@@ -122,9 +128,9 @@ fn round(monkeys: &mut Vec<Monkey>, mod_by: i64, divide_by: i64) -> Result<(), E
         let monkey = &monkeys[i];
         let target_monkeys = monkey.target_monkeys;
 
-        // get_many_mut ensures we're not aliasing any monkeys
+        // get_disjoint_mut ensures we're not aliasing any monkeys
         let [monkey, monkey_success, monkey_failure] =
-            monkeys.get_many_mut::<3>([i, target_monkeys.0, target_monkeys.1])?;
+            monkeys.get_disjoint_mut([i, target_monkeys.0, target_monkeys.1])?;
 
         for j in 0..monkey.items.len() {
             let mut item = monkey.items[j];
@@ -163,15 +169,22 @@ fn monkey_business(mut monkeys: Vec<Monkey>, rounds: usize, divide_by: i64) -> u
         * inspection_counts[inspection_counts.len() - 2]) as u64
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
-    let data = include_str!("../../input/day11.txt");
-    let monkeys: Vec<Monkey> = data.split("\n\n").map(|s| s.parse().unwrap()).collect();
+struct Puzzle;
+
+impl Solver for Puzzle {
+    type Parsed = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        input.split("\n\n").map(|s| s.parse()).collect()
+    }
 
-    let sol1: u64 = monkey_business(monkeys.clone(), 20, 3);
-    let sol2: u64 = monkey_business(monkeys, 10000, 1);
+    fn part_1(monkeys: &Self::Parsed) -> Solution {
+        Solution::U64(monkey_business(monkeys.clone(), 20, 3))
+    }
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+    fn part_2(monkeys: &Self::Parsed) -> Solution {
+        Solution::U64(monkey_business(monkeys.clone(), 10000, 1))
+    }
 }
 
 #[cfg(test)]
@@ -4,54 +4,25 @@ use std::{
 };
 
 use crate::{
-    etc::{ErasedError, Graph, Matrix, Node},
-    Solution, SolutionPair,
+    days::{Day, Solver},
+    etc::{graph::manhattan, grid::parse_grid, ErasedError, Graph, Grid},
+    Solution,
 };
 
-///////////////////////////////////////////////////////////////////////////////
-const INPUT: &str = include_str!("../../input/day12.txt");
+pub const DAY: Day = Day::new::<Puzzle>(12, "Hill Climbing Algorithm");
 
+///////////////////////////////////////////////////////////////////////////////
 /////////////
 #[derive(Debug, Clone)]
-struct PathProblem<const M: usize, const N: usize>
-where
-    [u8; M * N]:,
-{
-    map: Matrix<M, N, u8>,
+struct PathProblem {
+    map: Grid<u8>,
     start_index: [usize; 2],
     end_index: [usize; 2],
 }
 
-impl<const M: usize, const N: usize> PathProblem<M, N>
-where
-    [u8; M * N]:,
-{
+impl PathProblem {
     fn neighbors(&self, idx: [usize; 2]) -> impl Iterator<Item = [usize; 2]> {
-        let [i, j] = idx;
-        let mut row_indices = [None; 4];
-        let mut col_indices = [None; 4];
-        if i > 0 && i < M {
-            row_indices[0] = Some(i - 1);
-            col_indices[0] = Some(j);
-        }
-        if j > 0 && j < N {
-            row_indices[1] = Some(i);
-            col_indices[1] = Some(j - 1);
-        }
-
-        if j < N - 1 {
-            row_indices[2] = Some(i);
-            col_indices[2] = Some(j + 1);
-        }
-
-        if i < M - 1 {
-            row_indices[3] = Some(i + 1);
-            col_indices[3] = Some(j)
-        }
-        row_indices
-            .into_iter()
-            .zip(col_indices.into_iter())
-            .filter_map(|(idx, idy)| Some([idx?, idy?]))
+        self.map.bounds().neighbors(idx)
     }
 
     fn moves(&self, idx: [usize; 2]) -> impl Iterator<Item = [usize; 2]> + '_ {
@@ -59,115 +30,77 @@ where
             .filter(move |&nidx| self.map[nidx] <= self.map[idx] + 1)
     }
 
-    fn to_graph(&self) -> Graph {
-        let mut nodes = Vec::with_capacity(M * N);
-        // Generate all Nodes
-        for _ in 0..M * N {
-            nodes.push(Node::default())
+    /// The map as a graph of the allowed moves, labelled by index. Node ids are the cell
+    /// numbers `cols * i + j`.
+    fn to_graph(&self) -> Graph<[usize; 2]> {
+        let n = self.map.cols();
+        let mut graph = Graph::new();
+        for idx in self.map.each_index() {
+            graph.add_node(idx);
         }
-
-        // Add Edges to Graph
         for idx in self.map.each_index() {
-            let node = &mut nodes[N * idx[0] + idx[1]];
             for nidx in self.moves(idx) {
-                node.neighbors.push(N * nidx[0] + nidx[1])
+                graph.add_edge(n * idx[0] + idx[1], n * nidx[0] + nidx[1], 1);
             }
         }
-
-        Graph {
-            start: vec![N * self.start_index[0] + self.start_index[1]],
-            destination: N * self.end_index[0] + self.end_index[1],
-            nodes: nodes,
-        }
+        graph
     }
 
-    fn to_graph_target_level(&self, target_level: u8) -> Graph {
-        let mut nodes = Vec::with_capacity(M * N);
-        let mut starts = Vec::with_capacity(M * N);
-
-        for _ in 0..M * N {
-            nodes.push(Node::default())
-        }
+    /// Steps from the nearest of `starts` to the end, `None` if the end can't be reached.
+    fn shortest_path_len(&self, starts: impl IntoIterator<Item = [usize; 2]>) -> Option<u64> {
+        let graph = self.to_graph();
+        let id = |idx| graph.id(&idx).expect("ERROR: Index outside of the map.");
+        let search = graph.bfs(starts.into_iter().map(id));
+        search.distance(id(self.end_index))
+    }
 
-        // Add Edges to Graph
-        for idx in self.map.each_index() {
-            let node = &mut nodes[N * idx[0] + idx[1]];
-            for nidx in self.moves(idx) {
-                node.neighbors.push(N * nidx[0] + nidx[1])
-            }
-            if self.map[idx] == target_level {
-                starts.push(N * idx[0] + idx[1]);
-            }
-        }
+    /// Every step costs one, so the straight distance to the end is a lower bound.
+    fn shortest_path_len_astar(&self) -> Option<u64> {
+        let graph = self.to_graph();
+        let id = |idx| graph.id(&idx).expect("ERROR: Index outside of the map.");
+        let search = graph.astar([id(self.start_index)], id(self.end_index), |node| {
+            manhattan(*graph.label(node), self.end_index)
+        });
+        let path = search.shortest_path(id(self.end_index))?;
+        Some(path.steps() as u64)
+    }
 
-        Graph {
-            start: starts,
-            destination: N * self.end_index[0] + self.end_index[1],
-            nodes: nodes,
-        }
+    fn starts_at_level(&self, target_level: u8) -> impl Iterator<Item = [usize; 2]> + '_ {
+        self.map
+            .each_index()
+            .filter(move |&idx| self.map[idx] == target_level)
     }
 }
 
-impl<const M: usize, const N: usize> FromStr for PathProblem<M, N>
-where
-    [u8; M * N]:,
-{
+impl FromStr for PathProblem {
     type Err = ErasedError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        const START_CHAR: u8 = 'S' as u8;
-        const END_CHAR: u8 = 'E' as u8;
-
-        const MSG: &str = "ERROR: Parsing PathProblem failed.";
-        let s = s.trim();
-        if !s.trim().len() == M * N + N - 1 {
-            return Err(MSG.into());
-        }
-        let mut map = Matrix::new(0);
-
-        let mut start_index = None;
-        let mut end_index = None;
-        for (idx, mut height) in s
-            .chars()
-            .filter(|c| c.is_ascii_alphanumeric())
-            .map(|c| c as u8)
-            .enumerate()
-        {
-            if height == START_CHAR {
-                height = 'a' as u8;
-                start_index = Some([idx / N, idx % N])
-            } else if height == END_CHAR {
-                height = 'z' as u8;
-                end_index = Some([idx / N, idx % N])
-            }
-            map[idx] = height;
-        }
-        if let Some(start_index) = start_index {
-            if let Some(end_index) = end_index {
-                return Ok(PathProblem {
-                    map: map,
-                    start_index,
-                    end_index,
-                });
-            }
-        }
-        Err(MSG.into())
+        let parsed = parse_grid(s, &['S', 'E'], |c| match c {
+            'S' => Some(b'a'),
+            'E' => Some(b'z'),
+            'a'..='z' => Some(c as u8),
+            _ => None,
+        })?;
+        Ok(PathProblem {
+            start_index: parsed.marker('S')?,
+            end_index: parsed.marker('E')?,
+            map: parsed.grid,
+        })
     }
 }
 
-impl<const M: usize, const N: usize> Display for PathProblem<M, N>
-where
-    [(); M * N]:,
-{
+impl Display for PathProblem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let n = self.map.cols();
         for (i, h) in self.map.iter().enumerate() {
             let h = *h as char;
-            if i % N == 0 {
+            if i % n == 0 {
                 writeln!(f)?
             }
-            if i / N == self.start_index[0] && i % N == self.start_index[1] {
+            if i / n == self.start_index[0] && i % n == self.start_index[1] {
                 write!(f, "\x1b[36;1m {h:3}\x1b[0m")?
-            } else if i / N == self.end_index[0] && i % N == self.end_index[1] {
+            } else if i / n == self.end_index[0] && i % n == self.end_index[1] {
                 write!(f, "\x1b[35;1m {h:3}\x1b[0m")?
             } else {
                 write!(f, " {h:3}")?
@@ -177,18 +110,24 @@ where
     }
 }
 
-pub fn solve() -> SolutionPair {
-    let pp: PathProblem<41, 162> = INPUT.parse().unwrap();
+struct Puzzle;
 
-    let mut g1 = pp.to_graph();
-    g1.djikstra();
-    let sol1: u64 = g1.shortest_path_len();
+impl Solver for Puzzle {
+    type Parsed = PathProblem;
 
-    let mut g2 = pp.to_graph_target_level('a' as u8);
-    g2.djikstra();
-    let sol2: u64 = g2.shortest_path_len();
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        input.parse()
+    }
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+    fn part_1(pp: &Self::Parsed) -> Solution {
+        pp.shortest_path_len_astar()
+            .map_or(Solution::Unsolved, Solution::U64)
+    }
+
+    fn part_2(pp: &Self::Parsed) -> Solution {
+        pp.shortest_path_len(pp.starts_at_level(b'a'))
+            .map_or(Solution::Unsolved, Solution::U64)
+    }
 }
 
 #[cfg(test)]
@@ -196,23 +135,28 @@ mod tests {
 
     use super::*;
     const TEST_INPUT: &str = include_str!("../../input/day12_test.txt");
+    #[cfg(feature = "baked-input")]
+    const INPUT: &str = include_str!("../../input/day12.txt");
 
     #[test]
     fn from_str() {
-        let pp: PathProblem<5, 8> = TEST_INPUT.parse().unwrap();
+        let pp: PathProblem = TEST_INPUT.parse().unwrap();
         println!("{pp}");
+        assert_eq!((pp.map.rows(), pp.map.cols()), (5, 8));
+        assert!("Sabc\nabE".parse::<PathProblem>().is_err());
 
-        let pp: PathProblem<41, 162> = INPUT.parse().unwrap();
-        println!("{:?}", &pp.start_index);
-        println!("{:?}", &pp.end_index);
-        println!("{pp}");
+        #[cfg(feature = "baked-input")]
+        {
+            let pp: PathProblem = INPUT.parse().unwrap();
+            println!("{:?}", &pp.start_index);
+            println!("{:?}", &pp.end_index);
+            println!("{pp}");
+        }
     }
 
     #[test]
     fn neighbors() {
-        const M: usize = 5;
-        const N: usize = 8;
-        let pp: PathProblem<M, N> = TEST_INPUT.parse().unwrap();
+        let pp: PathProblem = TEST_INPUT.parse().unwrap();
 
         let n_ul: Vec<[usize; 2]> = pp.neighbors([0, 0]).collect();
         assert_eq!(n_ul, vec![[0, 1], [1, 0]]);
@@ -224,30 +168,37 @@ mod tests {
 
     #[test]
     fn to_graph() {
-        let pp: PathProblem<5, 8> = TEST_INPUT.parse().unwrap();
-        let mut graph = pp.to_graph();
-        graph.djikstra();
-        assert_eq!(graph.nodes[0].parent, None);
+        let pp: PathProblem = TEST_INPUT.parse().unwrap();
+        let graph = pp.to_graph();
+        let search = graph.dijkstra([0]);
+        assert_eq!(search.parent(0), None);
         for i in 1..graph.len() {
-            assert!(graph.nodes[i].parent.is_some())
+            assert!(search.parent(i).is_some())
         }
     }
     #[test]
     fn shortest_path() {
-        let pp: PathProblem<5, 8> = TEST_INPUT.parse().unwrap();
-        let mut graph = pp.to_graph();
-        graph.djikstra();
-        let path = graph.shortest_path();
-        assert_eq!(path.len(), 31);
-
-        assert_eq!(graph.shortest_path_len(), 31);
+        let pp: PathProblem = TEST_INPUT.parse().unwrap();
+        let graph = pp.to_graph();
+        let search = graph.dijkstra(graph.id(&pp.start_index));
+        let path = search
+            .shortest_path(graph.id(&pp.end_index).unwrap())
+            .unwrap();
+        let path = path.map(|id| *graph.label(id));
+        assert_eq!(path.steps(), 31);
+        assert_eq!(path.cost(), 31);
+        assert_eq!((*path.start(), *path.end()), (pp.start_index, pp.end_index));
+
+        assert_eq!(pp.shortest_path_len([pp.start_index]), Some(31));
+        assert_eq!(pp.shortest_path_len_astar(), Some(31));
+        assert_eq!(pp.shortest_path_len(pp.starts_at_level(b'a')), Some(29));
+        assert_eq!(pp.shortest_path_len([pp.end_index]), Some(0));
     }
 
     #[test]
+    #[cfg(feature = "baked-input")]
     fn part_1() {
-        let pp: PathProblem<41, 162> = INPUT.parse().unwrap();
-        let mut g = pp.to_graph();
-        g.djikstra();
-        println!("{:?}", g.shortest_path_len());
+        let pp: PathProblem = INPUT.parse().unwrap();
+        println!("{:?}", pp.shortest_path_len([pp.start_index]));
     }
 }
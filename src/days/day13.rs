@@ -1,6 +1,12 @@
 use std::{cmp, fmt::Debug, str::FromStr};
 
-use crate::{etc::ErasedError, Solution, SolutionPair};
+use crate::{
+    days::{Day, Solver},
+    etc::ErasedError,
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(13, "Distress Signal");
 
 ///////////////////////////////////////////////////////////////////////////////
 
@@ -258,33 +264,49 @@ fn read_data(s: &str) -> Result<Vec<(Packet, Packet)>, ErasedError> {
     Ok(out)
 }
 
-pub fn solve() -> SolutionPair {
-    const INPUT: &str = include_str!("../../input/day13.txt");
-    let pairs = read_data(INPUT).unwrap();
+fn part_1(pairs: &[(Packet, Packet)]) -> u64 {
     let mut idx_sum: u64 = 0;
     for (i, (left, right)) in pairs.iter().enumerate() {
         if left.three_cmp(right).is_less() {
             idx_sum += i as u64 + 1;
         }
     }
+    idx_sum
+}
 
-    let mut input_2 = INPUT.replace("\n\n", "\n").trim().to_string();
-    input_2.push_str("\n[[2]]\n[[6]]");
-    let mut packets: Vec<Packet> = input_2.lines().map(|l| l.parse().unwrap()).collect();
-    packets.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
+fn part_2(pairs: &[(Packet, Packet)]) -> u64 {
     let p2: Packet = "[[2]]".parse().unwrap();
     let p6: Packet = "[[6]]".parse().unwrap();
 
-    let sol1: u64 = idx_sum;
-    let sol2: u64 = packets
+    let mut packets: Vec<&Packet> = pairs.iter().flat_map(|(l, r)| [l, r]).collect();
+    packets.push(&p2);
+    packets.push(&p6);
+    packets.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    packets
         .into_iter()
         .enumerate()
-        .filter(|(_, p)| *p == p2 || *p == p6)
+        .filter(|(_, p)| **p == p2 || **p == p6)
         .map(|(i, _)| i as u64 + 1)
-        .product();
+        .product()
+}
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+struct Puzzle;
+
+impl Solver for Puzzle {
+    type Parsed = Vec<(Packet, Packet)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        read_data(input)
+    }
+
+    fn part_1(pairs: &Self::Parsed) -> Solution {
+        Solution::U64(part_1(pairs))
+    }
+
+    fn part_2(pairs: &Self::Parsed) -> Solution {
+        Solution::U64(part_2(pairs))
+    }
 }
 
 #[cfg(test)]
@@ -1,238 +1,268 @@
-use std::str::FromStr;
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 
 use crate::{
-    etc::{ErasedError, Matrix},
-    Solution, SolutionPair,
+    days::{Day, Solver, Visualize},
+    etc::{grid::Dir8, ErasedError, Grid},
+    Solution,
 };
 
+pub const DAY: Day = Day::new::<Puzzle>(14, "Regolith Reservoir").visualized::<Puzzle>();
+
 ///////////////////////////////////////////////////////////////////////////////
-const INPUT: &str = include_str!("../../input/day14.txt");
+
+/// Sand spawns at (500, 0).
+const SAND_SOURCE: (usize, usize) = (500, 0);
 
 #[derive(Debug, Clone)]
-struct Cave<const M: usize, const N: usize>
-where
-    [(); N * M]:,
-{
+struct Cave {
+    /// The grain that is falling. It is not part of the map until it comes to rest.
     active_sand_idx: [usize; 2],
-    x_offset: usize,
+    /// Where sand spawns, in map indices.
+    source: [usize; 2],
     floor_level: usize,
-    map: Box<Matrix<N, M, char>>,
+    map: Grid<char>,
 }
 
-impl<const M: usize, const N: usize> FromStr for Cave<M, N>
-where
-    [(); N * M]:,
-{
+impl FromStr for Cave {
     type Err = ErasedError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map = Box::new(Matrix::new('.'));
-
-        let x_offset = 500 - N;
-
-        let floor_level = s
+        let paths: Vec<Vec<(usize, usize)>> = s
             .lines()
-            .map(|l| {
-                l.split(" -> ")
+            .map(|line| {
+                line.split(" -> ")
                     .filter_map(|s| s.split_once(','))
                     .filter_map(|(i, j)| Some((i.parse::<usize>().ok()?, j.parse::<usize>().ok()?)))
-                    .map(|(_, y)| y)
-                    .max()
-                    .unwrap_or(0)
+                    .collect()
             })
-            .max()
-            .unwrap_or(0)
-            + 2; // Floor is places 2 below the bottom floor.
-
-        if floor_level >= N {
-            return Err(format!(
-                "ERROR: Floor at level {} out of bounds ({}, {}).",
-                floor_level, N, M
-            )
-            .into());
+            .collect();
+        if paths.iter().any(|steps| steps.len() < 2) {
+            return Err("ERROR: Path too short.".into());
         }
 
-        for line in s.lines() {
-            let steps: Vec<(usize, usize)> = line
-                .split(" -> ")
-                .filter_map(|s| s.split_once(','))
-                .filter_map(|(i, j)| Some((i.parse::<usize>().ok()?, j.parse::<usize>().ok()?)))
-                .collect();
+        // The floor is 2 below the lowest rock.
+        let floor_level = paths.iter().flatten().map(|&(_, y)| y).max().unwrap_or(0) + 2;
 
-            if steps.len() < 2 {
-                return Err("ERROR: Path too short.".into());
-            }
+        // Sand piles up at most `floor_level` to either side of the source, the floor reaches
+        // one further so sand can look past the pile.
+        let (source_x, _) = SAND_SOURCE;
+        let xs = || paths.iter().flatten().map(|&(x, _)| x);
+        let x_min = xs()
+            .chain([source_x.saturating_sub(floor_level + 1)])
+            .min()
+            .unwrap_or(0);
+        let x_max = xs().chain([source_x + floor_level + 1]).max().unwrap_or(0);
+        let mut map = Grid::new(floor_level + 1, x_max - x_min + 1, '.');
 
+        for steps in &paths {
             for [prev, snd] in steps.array_windows::<2>() {
                 for x in prev.0.min(snd.0)..=prev.0.max(snd.0) {
                     for y in prev.1.min(snd.1)..=prev.1.max(snd.1) {
-                        map[[y, x - x_offset]] = '#'
+                        map[[y, x - x_min]] = '#'
                     }
                 }
             }
         }
-        // Sand spawns at (500, 0);
-        let active_sand_idx = [0, 500 - x_offset];
+        let source = [SAND_SOURCE.1, source_x - x_min];
         Ok(Cave {
-            active_sand_idx,
-            x_offset,
+            active_sand_idx: source,
+            source,
             floor_level,
             map,
         })
     }
 }
 
-impl<const M: usize, const N: usize> Cave<M, N>
-where
-    [(); N * M]:,
-{
+impl Cave {
     fn spawn_sand(&mut self) -> bool {
-        self.active_sand_idx = [0, 500 - self.x_offset];
-        if self.map[self.active_sand_idx] == '.' {
-            self.map[self.active_sand_idx] = '+';
-            return true;
-        }
-        false
+        self.active_sand_idx = self.source;
+        self.map[self.source] == '.'
     }
 
     fn step(&mut self) -> bool {
         // We have exited the map
-        if self.look_down() == ' ' {
+        if self.look(Dir8::Down) == ' ' {
             return false;
-        } else if self.look_down() == '.' {
-            self.map[self.active_sand_idx] = '.';
-            self.active_sand_idx[0] += 1;
-            self.map[self.active_sand_idx] = '+';
-            return true;
-        } else if self.look_down_left() == '.' {
-            self.map[self.active_sand_idx] = '.';
-            self.active_sand_idx[0] += 1;
-            self.active_sand_idx[1] -= 1;
-            self.map[self.active_sand_idx] = '+';
-            return true;
-        } else if self.look_down_right() == '.' {
-            self.map[self.active_sand_idx] = '.';
-            self.active_sand_idx[0] += 1;
-            self.active_sand_idx[1] += 1;
-            self.map[self.active_sand_idx] = '+';
-            return true;
-        } else {
-            self.map[self.active_sand_idx] = 'o';
-            return self.spawn_sand();
         }
+        for direction in [Dir8::Down, Dir8::DownLeft, Dir8::DownRight] {
+            if self.look(direction) == '.' {
+                self.active_sand_idx = self
+                    .map
+                    .bounds()
+                    .step(self.active_sand_idx, direction)
+                    .expect("ERROR: Sand moved off the map.");
+                return true;
+            }
+        }
+        self.map[self.active_sand_idx] = 'o';
+        self.spawn_sand()
     }
 
+    /// The cell next to the falling sand, `' '` outside the map.
     #[inline]
-    fn look_down(&self) -> char {
-        let [mut y, x] = self.active_sand_idx;
-        y += 1;
-        *self.map.get(y, x).unwrap_or(&' ')
-    }
-
-    #[inline]
-    fn look_down_left(&self) -> char {
-        let [mut y, mut x] = self.active_sand_idx;
-        y += 1;
-        x -= 1;
-        *self.map.get(y, x).unwrap_or(&' ')
-    }
-
-    #[inline]
-    fn look_down_right(&self) -> char {
-        let [mut y, mut x] = self.active_sand_idx;
-        y += 1;
-        x += 1;
-        *self.map.get(y, x).unwrap_or(&' ')
+    fn look(&self, direction: Dir8) -> char {
+        self.map
+            .bounds()
+            .step(self.active_sand_idx, direction)
+            .map_or(' ', |idx| self.map[idx])
     }
 
     fn count_sand(&self) -> u64 {
-        self.map.iter().map(|el| (*el == 'o') as u64).sum()
+        self.map.iter().map(|&el| (el == 'o') as u64).sum()
     }
 
     fn add_floor(&mut self) {
-        for x in 0..M {
-            self.map[[self.floor_level, x]] = '#';
+        for j in 0..self.map.cols() {
+            self.map[[self.floor_level, j]] = '#'
+        }
+    }
+}
+
+/// The map with the falling grain drawn as `+`.
+impl Display for Cave {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.map.rows() {
+            for j in 0..self.map.cols() {
+                match [i, j] == self.active_sand_idx {
+                    true => write!(f, "+")?,
+                    false => write!(f, "{}", self.map[[i, j]])?,
+                }
+            }
+            writeln!(f)?
         }
+        Ok(())
     }
 }
 
-fn part_1<const M: usize, const N: usize>(cave: &mut Cave<M, N>) -> u64
-where
-    [(); N * M]:,
-{
+fn part_1(cave: &mut Cave) -> u64 {
     while cave.step() {}
     cave.count_sand()
 }
 
-fn part_2<const M: usize, const N: usize>(cave: &mut Cave<M, N>) -> u64
-where
-    [(); N * M]:,
-{
+fn part_2(cave: &mut Cave) -> u64 {
     cave.add_floor();
     while cave.step() {}
     cave.count_sand()
 }
 
-pub fn solve() -> SolutionPair {
-    let mut cave_1: Cave<320, 160> = INPUT.parse().unwrap();
-    let mut cave_2 = cave_1.clone();
-    let sol1: u64 = part_1(&mut cave_1);
-    let sol2: u64 = part_2(&mut cave_2);
+struct Puzzle;
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+/// The sand of part 1 falling step by step, until it runs off into the abyss. Every frame
+/// shows the whole map, floor space included, so they all have the same size.
+impl Visualize for Puzzle {
+    fn frames(mut cave: Self::Parsed) -> impl Iterator<Item = String> + 'static {
+        cave.spawn_sand();
+        let first = cave.to_string();
+        std::iter::once(first).chain(std::iter::from_fn(move || {
+            cave.step().then(|| cave.to_string())
+        }))
+    }
+}
+
+impl Solver for Puzzle {
+    type Parsed = Cave;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        input.parse()
+    }
+
+    fn part_1(cave: &Self::Parsed) -> Solution {
+        Solution::U64(part_1(&mut cave.clone()))
+    }
+
+    fn part_2(cave: &Self::Parsed) -> Solution {
+        Solution::U64(part_2(&mut cave.clone()))
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::{thread, time::Duration};
+    use std::env;
 
     use super::*;
+    use crate::etc::image::{Frames, Image};
     const TEST_INPUT: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9";
-    const TEST_M: usize = 24; // 2 * N - 1
-    const TEST_N: usize = 12;
+    #[cfg(feature = "baked-input")]
+    const INPUT: &str = include_str!("../../input/day14.txt");
+
+    /// Rock in grey, resting sand in yellow and the falling grain in orange.
+    fn image(cave: &Cave) -> Image {
+        let mut map = cave.map.clone();
+        map[cave.active_sand_idx] = '+';
+        Image::from_cells(&map, |cell| match cell {
+            '#' => [128, 128, 128],
+            'o' => [230, 190, 80],
+            '+' => [255, 120, 0],
+            _ => [20, 20, 30],
+        })
+    }
 
     #[test]
     fn from_str() {
         // We get the generics right. Kinda hadr to find good test conditions.
-        let mut cave: Cave<TEST_M, TEST_N> = TEST_INPUT.parse().unwrap();
+        let mut cave: Cave = TEST_INPUT.parse().unwrap();
         assert_eq!(cave.floor_level, 11);
         cave.add_floor();
         println!("{}", cave.map);
     }
 
     #[test]
+    fn frames() {
+        let cave: Cave = TEST_INPUT.parse().unwrap();
+        let frames: Vec<String> = Puzzle::frames(cave).collect();
+        assert_eq!(frames[0].lines().next(), Some("............+............"));
+        // Every frame shows the whole cave, so they all have the same size.
+        assert!(frames.iter().all(|frame| frame.len() == frames[0].len()));
+        let last = frames.last().unwrap();
+        assert_eq!(last.matches('o').count(), 24);
+        assert_eq!(last.matches('+').count(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "baked-input")]
     fn from_str_large() {
-        let cave: Cave<320, 160> = INPUT.parse().unwrap();
+        let cave: Cave = INPUT.parse().unwrap();
         println!("{}", cave.map);
     }
 
     #[test]
     fn test_part_1() {
-        let mut cave: Cave<TEST_M, TEST_N> = TEST_INPUT.parse().unwrap();
+        let mut cave: Cave = TEST_INPUT.parse().unwrap();
 
-        cave.spawn_sand();
-        println!("{}", cave.map);
-
-        while cave.step() {
-            thread::sleep(Duration::from_millis(1000 / 30));
-            print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-            println!("{}", cave.map);
-        }
+        while cave.step() {}
+        println!("{cave}");
         assert_eq!(cave.count_sand(), 24);
     }
 
     #[test]
     fn test_part_2() {
-        let mut cave: Cave<TEST_M, TEST_N> = TEST_INPUT.parse().unwrap();
+        let mut cave: Cave = TEST_INPUT.parse().unwrap();
         cave.add_floor();
-        cave.spawn_sand();
-        println!("{}", cave.map);
+        while cave.step() {}
+        println!("{cave}");
+        assert_eq!(cave.count_sand(), 93);
+    }
 
-        while cave.step() {
-            thread::sleep(Duration::from_millis(1000 / 30));
-            print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-            println!("{}", cave.map);
+    /// Writes the falling sand of both parts as frames into `$TMPDIR/aoc-day14/`, to look at
+    /// or stitch together into an animation. Run with `cargo test dump_frames -- --ignored`.
+    #[test]
+    #[ignore]
+    fn dump_frames() {
+        let dir = env::temp_dir().join("aoc-day14");
+        for (prefix, floor) in [("part_1", false), ("part_2", true)] {
+            let mut cave: Cave = TEST_INPUT.parse().unwrap();
+            if floor {
+                cave.add_floor();
+            }
+            let mut frames = Frames::new(&dir, prefix).unwrap();
+            frames.push(&image(&cave).scaled(8)).unwrap();
+            while cave.step() {
+                frames.push(&image(&cave).scaled(8)).unwrap();
+            }
+            println!("{} frames in {}", frames.len(), dir.display());
         }
-        assert_eq!(cave.count_sand(), 93);
     }
 }
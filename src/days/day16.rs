@@ -1,11 +1,219 @@
-use crate::{Solution, SolutionPair};
+use std::{cmp::Reverse, collections::HashMap, str::FromStr};
+
+use crate::{
+    days::{Day, Solver},
+    etc::{graph::NodeId, ErasedError, Graph},
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(16, "Proboscidea Volcanium");
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
-    let sol1: u64 = 0;
-    let sol2: u64 = 0;
+const START: &str = "AA";
+
+#[derive(Debug, Clone, PartialEq)]
+struct Valve {
+    name: String,
+    flow: u32,
+    tunnels: Vec<String>,
+}
+
+impl FromStr for Valve {
+    type Err = ErasedError;
+
+    /// Parses `Valve AA has flow rate=0; tunnels lead to valves DD, II, BB`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const ERROR_MSG: &str = "ERROR: Parsing valve failed";
+        let (valve, tunnels) = s.split_once("; ").ok_or(ERROR_MSG)?;
+        let (name, flow) = valve
+            .strip_prefix("Valve ")
+            .and_then(|valve| valve.split_once(" has flow rate="))
+            .ok_or(ERROR_MSG)?;
+        let tunnels = tunnels
+            .strip_prefix("tunnels lead to valves ")
+            .or_else(|| tunnels.strip_prefix("tunnel leads to valve "))
+            .ok_or(ERROR_MSG)?;
+
+        Ok(Valve {
+            name: name.to_string(),
+            flow: flow.parse()?,
+            tunnels: tunnels.split(", ").map(str::to_string).collect(),
+        })
+    }
+}
+
+/// The start and the valves worth opening, with the minutes it takes to walk between them.
+#[derive(Debug, Clone)]
+struct Volcano {
+    graph: Graph<String, u32>,
+    flows: Vec<u32>,
+    start: NodeId,
+}
+
+impl FromStr for Volcano {
+    type Err = ErasedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let valves: Vec<Valve> = s
+            .lines()
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?;
+
+        let mut tunnels = Graph::new();
+        for valve in &valves {
+            tunnels.add_node(valve.name.clone());
+        }
+        if tunnels.len() != valves.len() {
+            return Err("ERROR: Valve names are not unique.".into());
+        }
+        for (from, valve) in valves.iter().enumerate() {
+            for name in &valve.tunnels {
+                let to = tunnels
+                    .id(name)
+                    .ok_or_else(|| format!("ERROR: Tunnel to unknown valve {name}."))?;
+                tunnels.add_edge(from, to, 1);
+            }
+        }
+
+        let start = tunnels
+            .id(&START.to_string())
+            .ok_or("ERROR: There is no valve AA to start at.")?;
+        let keep: Vec<NodeId> = std::iter::once(start)
+            .chain(
+                valves
+                    .iter()
+                    .enumerate()
+                    .filter(|(id, valve)| valve.flow > 0 && *id != start)
+                    .map(|(id, _)| id),
+            )
+            .collect();
+        if keep.len() > u64::BITS as usize {
+            return Err("ERROR: Too many valves with a flow to keep track of.".into());
+        }
+
+        let graph = tunnels.compress(keep.iter().copied());
+        let flows = keep.iter().map(|&id| valves[id].flow).collect();
+        Ok(Volcano {
+            graph,
+            flows,
+            start: 0,
+        })
+    }
+}
+
+impl Volcano {
+    /// The most pressure that can be released in `minutes` for every set of opened valves,
+    /// as a bit mask of their ids.
+    fn best_releases(&self, minutes: u32) -> HashMap<u64, u32> {
+        let mut best = HashMap::new();
+        self.explore(self.start, minutes, 0, 0, &mut best);
+        best
+    }
+
+    fn explore(
+        &self,
+        valve: NodeId,
+        minutes_left: u32,
+        opened: u64,
+        released: u32,
+        best: &mut HashMap<u64, u32>,
+    ) {
+        let entry = best.entry(opened).or_default();
+        *entry = released.max(*entry);
+
+        for &(next, distance) in self.graph.edges(valve) {
+            let bit = 1 << next;
+            // Walking there and opening the valve has to leave at least a minute of flow.
+            if opened & bit != 0 || self.flows[next] == 0 || distance + 1 >= minutes_left {
+                continue;
+            }
+            let left = minutes_left - distance - 1;
+            self.explore(
+                next,
+                left,
+                opened | bit,
+                released + self.flows[next] * left,
+                best,
+            );
+        }
+    }
+}
+
+/// The most pressure two explorers release together when each opens different valves.
+fn best_pair(best: &HashMap<u64, u32>) -> u32 {
+    let mut releases: Vec<(u64, u32)> = best
+        .iter()
+        .map(|(&opened, &released)| (opened, released))
+        .collect();
+    releases.sort_unstable_by_key(|&(_, released)| Reverse(released));
+
+    let mut most = 0;
+    for (i, &(mine, my_release)) in releases.iter().enumerate() {
+        for &(theirs, their_release) in &releases[i..] {
+            if my_release + their_release <= most {
+                break;
+            }
+            if mine & theirs == 0 {
+                most = my_release + their_release;
+            }
+        }
+    }
+    most
+}
+
+struct Puzzle;
+
+impl Solver for Puzzle {
+    type Parsed = Volcano;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        input.parse()
+    }
+
+    fn part_1(volcano: &Self::Parsed) -> Solution {
+        let best = volcano.best_releases(30);
+        Solution::U32(best.values().copied().max().unwrap_or_default())
+    }
+
+    fn part_2(volcano: &Self::Parsed) -> Solution {
+        Solution::U32(best_pair(&volcano.best_releases(26)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const TEST_INPUT: &str = include_str!("../../input/day16_test.txt");
+
+    #[test]
+    fn parse() {
+        let valve: Valve = "Valve HH has flow rate=22; tunnel leads to valve GG"
+            .parse()
+            .unwrap();
+        assert_eq!(valve.name, "HH");
+        assert_eq!(valve.flow, 22);
+        assert_eq!(valve.tunnels, vec!["GG".to_string()]);
+        assert!("Valve HH has flow rate=22".parse::<Valve>().is_err());
+
+        let volcano: Volcano = TEST_INPUT.parse().unwrap();
+        // AA and the six valves with a flow.
+        assert_eq!(volcano.graph.len(), 7);
+        assert_eq!(volcano.graph.label(volcano.start), "AA");
+        let hh = volcano.graph.id(&"HH".to_string()).unwrap();
+        assert_eq!(volcano.flows[hh], 22);
+        assert!(volcano.graph.edges(volcano.start).contains(&(hh, 5)));
+    }
+
+    #[test]
+    fn part_1() {
+        let volcano = Puzzle::parse(TEST_INPUT).unwrap();
+        assert_eq!(Puzzle::part_1(&volcano), Solution::U32(1651));
+    }
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+    #[test]
+    fn part_2() {
+        let volcano = Puzzle::parse(TEST_INPUT).unwrap();
+        assert_eq!(Puzzle::part_2(&volcano), Solution::U32(1707));
+    }
 }
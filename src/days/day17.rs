@@ -1,7 +1,9 @@
 #![allow(dead_code, unused)]
 use std::char::MAX;
 
-use crate::{Solution, SolutionPair};
+use crate::days::Day;
+
+pub const DAY: Day = Day::unimplemented(17, "Pyroclastic Flow");
 
 ///////////////////////////////////////////////////////////////////////////////
 
@@ -88,14 +90,6 @@ impl Rock {
     }
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
-    let sol1: u64 = 0;
-    let sol2: u64 = 0;
-
-    (Solution::U64(sol1), Solution::U64(sol2))
-}
-
 fn add2d(
     sprite: &[u8],
     pos: (usize, usize),
@@ -1,11 +1,5 @@
-use crate::{Solution, SolutionPair};
+use super::Day;
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
-    let sol1: u64 = 0;
-    let sol2: u64 = 0;
-
-    (Solution::U64(sol1), Solution::U64(sol2))
-}
+pub const DAY: Day = Day::unimplemented(21, "Monkey Math");
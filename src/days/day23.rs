@@ -1,11 +1,151 @@
-use crate::{Solution, SolutionPair};
+use std::{collections::HashMap, str::FromStr};
+
+use crate::{
+    days::{Day, Solver},
+    etc::{
+        grid::{
+            parse_grid,
+            sparse::{neighbors8, step},
+            Dir8, Point, SparseGrid,
+        },
+        ErasedError,
+    },
+    Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(23, "Unstable Diffusion");
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
-    let sol1: u64 = 0;
-    let sol2: u64 = 0;
+/// The directions elves consider moving in, in order, each with the three cells that have to
+/// be free for it.
+const PROPOSALS: [[Dir8; 3]; 4] = [
+    [Dir8::Up, Dir8::UpLeft, Dir8::UpRight],
+    [Dir8::Down, Dir8::DownLeft, Dir8::DownRight],
+    [Dir8::Left, Dir8::UpLeft, Dir8::DownLeft],
+    [Dir8::Right, Dir8::UpRight, Dir8::DownRight],
+];
+
+#[derive(Debug, Clone)]
+struct Grove {
+    elves: SparseGrid<()>,
+    round: usize,
+}
+
+impl FromStr for Grove {
+    type Err = ErasedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let map = parse_grid(s, &[], |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?
+        .grid;
+        let elves = map
+            .each_index()
+            .filter(|&idx| map[idx])
+            .map(|[i, j]| ([i as i64, j as i64], ()))
+            .collect();
+        Ok(Grove { elves, round: 0 })
+    }
+}
+
+impl Grove {
+    /// Plays one round. Returns whether any elf moved.
+    fn spread(&mut self) -> bool {
+        // Where elves want to go, `None` once two of them want the same cell.
+        let mut proposals: HashMap<Point, Option<Point>> = HashMap::new();
+        for elf in self.elves.points() {
+            if neighbors8(elf).all(|point| !self.elves.contains(point)) {
+                continue;
+            }
+            let free = |directions: &[Dir8; 3]| {
+                directions
+                    .iter()
+                    .all(|&direction| !self.elves.contains(step(elf, direction)))
+            };
+            if let Some(directions) = (0..4)
+                .map(|k| &PROPOSALS[(self.round + k) % 4])
+                .find(|directions| free(directions))
+            {
+                proposals
+                    .entry(step(elf, directions[0]))
+                    .and_modify(|proposal| *proposal = None)
+                    .or_insert(Some(elf));
+            }
+        }
+
+        let mut moved = false;
+        for (target, elf) in proposals {
+            if let Some(elf) = elf {
+                self.elves.remove(elf).expect("ERROR: Elf went missing.");
+                self.elves.insert(target, ());
+                moved = true;
+            }
+        }
+        self.round += 1;
+        moved
+    }
+
+    fn empty_ground(&self) -> u64 {
+        self.elves.area() - self.elves.len() as u64
+    }
+}
+
+struct Puzzle;
+
+impl Solver for Puzzle {
+    type Parsed = Grove;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError> {
+        input.parse()
+    }
+
+    fn part_1(grove: &Self::Parsed) -> Solution {
+        let mut grove = grove.clone();
+        for _ in 0..10 {
+            grove.spread();
+        }
+        Solution::U64(grove.empty_ground())
+    }
+
+    fn part_2(grove: &Self::Parsed) -> Solution {
+        let mut grove = grove.clone();
+        while grove.spread() {}
+        Solution::U64(grove.round as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const TEST_INPUT: &str = include_str!("../../input/day23_test.txt");
+
+    #[test]
+    fn spread() {
+        let mut grove: Grove = ".....\n..##.\n..#..\n.....\n..##.\n.....".parse().unwrap();
+        for _ in 0..3 {
+            assert!(grove.spread());
+        }
+        let picture: SparseGrid<char> = grove.elves.points().map(|elf| (elf, '#')).collect();
+        assert_eq!(
+            picture.to_string(),
+            "..#..\n....#\n#....\n....#\n.....\n..#..\n"
+        );
+        assert!(!grove.spread());
+        assert!("..#\n.x.".parse::<Grove>().is_err());
+    }
+
+    #[test]
+    fn part_1() {
+        let grove = Puzzle::parse(TEST_INPUT).unwrap();
+        assert_eq!(Puzzle::part_1(&grove), Solution::U64(110));
+    }
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+    #[test]
+    fn part_2() {
+        let grove = Puzzle::parse(TEST_INPUT).unwrap();
+        assert_eq!(Puzzle::part_2(&grove), Solution::U64(20));
+    }
 }
@@ -23,3 +23,222 @@ pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+
+use std::time::{Duration, Instant};
+
+use crate::etc::{ErasedError, Solution};
+
+/// The phases of a puzzle. The input is parsed once and both parts work on the parsed value.
+/// A part that is not solved yet can be left out, it answers `Solution::Unsolved`.
+pub trait Solver {
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ErasedError>;
+
+    fn part_1(_parsed: &Self::Parsed) -> Solution {
+        Solution::Unsolved
+    }
+
+    fn part_2(_parsed: &Self::Parsed) -> Solution {
+        Solution::Unsolved
+    }
+}
+
+/// A day whose solution is a simulation worth watching, frame by frame, with `--visualize`.
+pub trait Visualize: Solver {
+    /// The states of the simulation drawn as text, in order.
+    fn frames(parsed: Self::Parsed) -> impl Iterator<Item = String> + 'static;
+}
+
+/// Which of the two parts of a day to solve.
+pub type Parts = [bool; 2];
+
+pub const BOTH_PARTS: Parts = [true, true];
+
+/// Answers of a single run together with the time spent in each phase. Parts that were not
+/// selected have no answer and take no time.
+pub struct Run {
+    pub part_1: Option<Solution>,
+    pub part_2: Option<Solution>,
+    pub parse_time: Duration,
+    pub part_1_time: Duration,
+    pub part_2_time: Duration,
+}
+
+// Days are run on worker threads, so their answers have to be sendable.
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    assert_send::<Run>();
+};
+
+impl Run {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.part_1_time + self.part_2_time
+    }
+}
+
+fn run<S: Solver>(input: &str, parts: Parts) -> Result<Run, ErasedError> {
+    let time = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = time.elapsed();
+
+    let (part_1, part_1_time) = solve(parts[0], || S::part_1(&parsed));
+    let (part_2, part_2_time) = solve(parts[1], || S::part_2(&parsed));
+
+    Ok(Run {
+        part_1,
+        part_2,
+        parse_time,
+        part_1_time,
+        part_2_time,
+    })
+}
+
+fn solve(selected: bool, part: impl FnOnce() -> Solution) -> (Option<Solution>, Duration) {
+    if !selected {
+        return (None, Duration::ZERO);
+    }
+    let time = Instant::now();
+    let solution = part();
+    (Some(solution), time.elapsed())
+}
+
+/// Parses the input and solves the selected parts of a day.
+pub type RunFn = fn(&str, Parts) -> Result<Run, ErasedError>;
+
+pub type Frames = Box<dyn Iterator<Item = String>>;
+
+fn frames<V: Visualize>(input: &str) -> Result<Frames, ErasedError> {
+    Ok(Box::new(V::frames(V::parse(input)?)))
+}
+
+/// Parses the input and sets up the simulation of a day.
+pub type FramesFn = fn(&str) -> Result<Frames, ErasedError>;
+
+/// A puzzle of the calendar, as registered by its module.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub run: Option<RunFn>,
+    pub frames: Option<FramesFn>,
+}
+
+impl Day {
+    pub const fn new<S: Solver>(number: u8, title: &'static str) -> Self {
+        Day {
+            number,
+            title,
+            run: Some(run::<S>),
+            frames: None,
+        }
+    }
+
+    /// The same day, with a simulation to show with `--visualize`.
+    pub const fn visualized<V: Visualize>(self) -> Self {
+        Day {
+            frames: Some(frames::<V>),
+            ..self
+        }
+    }
+
+    /// A day whose module is still a stub.
+    pub const fn unimplemented(number: u8, title: &'static str) -> Self {
+        Day {
+            number,
+            title,
+            run: None,
+            frames: None,
+        }
+    }
+
+    pub fn is_implemented(&self) -> bool {
+        self.run.is_some()
+    }
+}
+
+/// Every day of the calendar, in order. Each module registers itself through its `DAY`.
+pub const DAYS: &[Day] = &[
+    day01::DAY,
+    day02::DAY,
+    day03::DAY,
+    day04::DAY,
+    day05::DAY,
+    day06::DAY,
+    day07::DAY,
+    day08::DAY,
+    day09::DAY,
+    day10::DAY,
+    day11::DAY,
+    day12::DAY,
+    day13::DAY,
+    day14::DAY,
+    day15::DAY,
+    day16::DAY,
+    day17::DAY,
+    day18::DAY,
+    day19::DAY,
+    day20::DAY,
+    day21::DAY,
+    day22::DAY,
+    day23::DAY,
+    day24::DAY,
+    day25::DAY,
+];
+
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// Puzzle inputs compiled into the binary, so benchmarks do not account for disk access.
+#[cfg(feature = "baked-input")]
+pub fn baked_input(day: u8) -> Option<&'static str> {
+    match day {
+        1 => Some(include_str!("../../input/day01.txt")),
+        2 => Some(include_str!("../../input/day02.txt")),
+        3 => Some(include_str!("../../input/day03.txt")),
+        4 => Some(include_str!("../../input/day04.txt")),
+        5 => Some(include_str!("../../input/day05.txt")),
+        6 => Some(include_str!("../../input/day06.txt")),
+        7 => Some(include_str!("../../input/day07.txt")),
+        8 => Some(include_str!("../../input/day08.txt")),
+        9 => Some(include_str!("../../input/day09.txt")),
+        10 => Some(include_str!("../../input/day10.txt")),
+        11 => Some(include_str!("../../input/day11.txt")),
+        12 => Some(include_str!("../../input/day12.txt")),
+        13 => Some(include_str!("../../input/day13.txt")),
+        14 => Some(include_str!("../../input/day14.txt")),
+        16 => Some(include_str!("../../input/day16.txt")),
+        23 => Some(include_str!("../../input/day23.txt")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry() {
+        assert_eq!(DAYS.len(), 25);
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.number as usize, i + 1);
+            assert!(!day.title.is_empty());
+        }
+        assert_eq!(
+            get(12).map(|day| day.title),
+            Some("Hill Climbing Algorithm")
+        );
+        assert!(get(26).is_none());
+        #[cfg(feature = "baked-input")]
+        for day in DAYS.iter().filter(|day| day.is_implemented()) {
+            assert!(
+                baked_input(day.number).is_some(),
+                "Day {} is not baked in.",
+                day.number
+            );
+        }
+        assert!(get(14).is_some_and(|day| day.frames.is_some()));
+        assert!(get(13).is_some_and(|day| day.frames.is_none()));
+    }
+}
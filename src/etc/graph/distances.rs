@@ -0,0 +1,56 @@
+use super::{NodeId, Weight};
+
+/// Shortest distances between every pair of nodes, as found by an all-pairs search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distances<W> {
+    len: usize,
+    distance: Vec<Option<W>>,
+}
+
+impl<W: Weight> Distances<W> {
+    /// Distances among `len` nodes where every node only reaches itself.
+    pub(super) fn new(len: usize) -> Self {
+        let mut distances = Distances {
+            len,
+            distance: vec![None; len * len],
+        };
+        for id in 0..len {
+            distances.set(id, id, W::default());
+        }
+        distances
+    }
+
+    pub(super) fn set(&mut self, from: NodeId, to: NodeId, distance: W) {
+        self.distance[from * self.len + to] = Some(distance);
+    }
+
+    /// Distance from `from` to `to`, `None` if there is no path.
+    pub fn distance(&self, from: NodeId, to: NodeId) -> Option<W> {
+        self.distance[from * self.len + to]
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let mut distances: Distances<u32> = Distances::new(3);
+        assert_eq!(distances.distance(1, 1), Some(0));
+        assert_eq!(distances.distance(0, 2), None);
+        distances.set(0, 2, 7);
+        assert_eq!(distances.distance(0, 2), Some(7));
+        assert_eq!(distances.distance(2, 0), None);
+        assert_eq!(distances.len(), 3);
+    }
+}
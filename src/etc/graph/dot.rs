@@ -0,0 +1,175 @@
+//! Graphviz export, e.g. `dot -Tsvg graph.dot -o graph.svg`.
+
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    hash::Hash,
+};
+
+use super::{Graph, NodeId, Path, Search, Weight};
+
+const START_COLOR: &str = "palegreen";
+const DESTINATION_COLOR: &str = "lightcoral";
+const TREE_COLOR: &str = "steelblue";
+const PATH_COLOR: &str = "red";
+
+/// A graph in the DOT language, with whatever is worth highlighting. Built by `Graph::dot`
+/// and written out through `Display`.
+pub struct Dot<'a, L, W> {
+    graph: &'a Graph<L, W>,
+    label: Box<dyn Fn(&L) -> String + 'a>,
+    starts: HashSet<NodeId>,
+    destination: Option<NodeId>,
+    tree: HashSet<(NodeId, NodeId)>,
+    path: HashSet<(NodeId, NodeId)>,
+    on_path: HashSet<NodeId>,
+}
+
+impl<L: Clone + Eq + Hash + Debug, W: Weight> Graph<L, W> {
+    /// The graph as DOT, with nodes labelled by their `Debug` form.
+    pub fn dot(&self) -> Dot<'_, L, W> {
+        Dot {
+            graph: self,
+            label: Box::new(|label| format!("{label:?}")),
+            starts: HashSet::new(),
+            destination: None,
+            tree: HashSet::new(),
+            path: HashSet::new(),
+            on_path: HashSet::new(),
+        }
+    }
+}
+
+impl<'a, L: Clone + Eq + Hash, W: Weight> Dot<'a, L, W> {
+    /// Labels nodes with `label` instead of their `Debug` form.
+    pub fn labels(mut self, label: impl Fn(&L) -> String + 'a) -> Self {
+        self.label = Box::new(label);
+        self
+    }
+
+    pub fn starts(mut self, starts: impl IntoIterator<Item = NodeId>) -> Self {
+        self.starts.extend(starts);
+        self
+    }
+
+    pub fn destination(mut self, destination: NodeId) -> Self {
+        self.destination = Some(destination);
+        self
+    }
+
+    /// Highlights the starts of a search and the edges it reached every node through.
+    pub fn tree<V: Weight>(mut self, search: &Search<V>) -> Self {
+        self.starts.extend(search.starts());
+        for v in 0..self.graph.len() {
+            if let Some(u) = search.parent(v) {
+                self.tree.insert((u, v));
+            }
+        }
+        self
+    }
+
+    /// Highlights a path, e.g. the shortest one to the destination.
+    pub fn path<V: Copy>(mut self, path: &Path<NodeId, V>) -> Self {
+        self.on_path.extend(path.nodes());
+        self.path
+            .extend(path.nodes().windows(2).map(|pair| (pair[0], pair[1])));
+        self
+    }
+}
+
+/// A DOT string literal. Newlines stay escaped, which DOT shows as line breaks.
+fn dot_string(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+impl<L: Clone + Eq + Hash, W: Weight> Display for Dot<'_, L, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "digraph {{")?;
+        writeln!(f, "    node [shape=box];")?;
+        for id in self.graph.node_ids() {
+            let label = dot_string(&(self.label)(self.graph.label(id)));
+            write!(f, "    {id} [label={label}")?;
+            let fill = if Some(id) == self.destination {
+                Some(DESTINATION_COLOR)
+            } else if self.starts.contains(&id) {
+                Some(START_COLOR)
+            } else {
+                None
+            };
+            if let Some(fill) = fill {
+                write!(f, ", style=filled, fillcolor={fill}")?;
+            }
+            if self.on_path.contains(&id) {
+                write!(f, ", color={PATH_COLOR}, penwidth=2")?;
+            }
+            writeln!(f, "];")?;
+        }
+        for u in self.graph.node_ids() {
+            for (v, weight) in self.graph.edges(u) {
+                let weight = dot_string(&format!("{weight:?}"));
+                write!(f, "    {u} -> {v} [label={weight}")?;
+                if self.path.contains(&(u, *v)) {
+                    write!(f, ", color={PATH_COLOR}, penwidth=2")?;
+                } else if self.tree.contains(&(u, *v)) {
+                    write!(f, ", color={TREE_COLOR}")?;
+                }
+                writeln!(f, "];")?;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain() {
+        let mut graph: Graph<&str> = Graph::new();
+        let [a, b] = ["a", "say \"b\""].map(|label| graph.add_node(label));
+        graph.add_edge(a, b, 3);
+        assert_eq!(
+            graph.dot().labels(|label| label.to_string()).to_string(),
+            "\
+digraph {
+    node [shape=box];
+    0 [label=\"a\"];
+    1 [label=\"say \\\"b\\\"\"];
+    0 -> 1 [label=\"3\"];
+}
+"
+        );
+    }
+
+    #[test]
+    fn highlights() {
+        // a -1-> b -1-> c and a -5-> c.
+        let mut graph: Graph<char> = Graph::new();
+        let [a, b, c] = ['a', 'b', 'c'].map(|label| graph.add_node(label));
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, c, 1);
+        graph.add_edge(a, c, 5);
+        let search = graph.dijkstra([a]);
+        let path = search.shortest_path(c).unwrap();
+        let dot = graph
+            .dot()
+            .tree(&search)
+            .destination(c)
+            .path(&path)
+            .to_string();
+        assert!(dot.contains("0 [label=\"'a'\", style=filled, fillcolor=palegreen, color=red"));
+        assert!(dot.contains("2 [label=\"'c'\", style=filled, fillcolor=lightcoral, color=red"));
+        assert!(dot.contains("1 -> 2 [label=\"1\", color=red, penwidth=2];"));
+        assert!(dot.contains("0 -> 2 [label=\"5\"];"));
+
+        // Without the path only the search tree stands out.
+        let dot = graph.dot().tree(&graph.bfs([a])).to_string();
+        assert!(dot.contains("0 -> 2 [label=\"5\", color=steelblue];"));
+        assert!(dot.contains("1 -> 2 [label=\"1\"];"));
+    }
+}
@@ -0,0 +1,270 @@
+//! Searches over states that are generated while searching instead of a `Graph` built up
+//! front. States are numbered as they are discovered, so the result is a `Search` like any
+//! other.
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use priority_queue::PriorityQueue;
+
+use super::{NodeId, Path, Search, Weight};
+
+/// Every state a search discovered together with what it found out about them.
+#[derive(Debug, Clone)]
+pub struct StateSearch<S, W> {
+    states: Vec<S>,
+    ids: HashMap<S, NodeId>,
+    search: Search<W>,
+    goal: Option<NodeId>,
+}
+
+impl<S: Clone + Eq + Hash, W: Weight> StateSearch<S, W> {
+    fn new(start: S) -> Self {
+        StateSearch {
+            ids: HashMap::from([(start.clone(), 0)]),
+            states: vec![start],
+            search: Search::new(1, [0]),
+            goal: None,
+        }
+    }
+
+    fn intern(&mut self, state: S) -> NodeId {
+        if let Some(&id) = self.ids.get(&state) {
+            return id;
+        }
+        let id = self.states.len();
+        self.ids.insert(state.clone(), id);
+        self.states.push(state);
+        self.search.grow(self.states.len());
+        id
+    }
+
+    /// The goal state the search stopped at, if it found one.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.map(|id| &self.states[id])
+    }
+
+    /// Cost of reaching the goal.
+    pub fn cost(&self) -> Option<W> {
+        self.search.distance(self.goal?)
+    }
+
+    /// Cost of reaching a state, `None` if the search never got there.
+    pub fn distance(&self, state: &S) -> Option<W> {
+        self.search.distance(*self.ids.get(state)?)
+    }
+
+    /// Number of distinct states discovered.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    pub fn id(&self, state: &S) -> Option<NodeId> {
+        self.ids.get(state).copied()
+    }
+
+    pub fn state(&self, id: NodeId) -> &S {
+        &self.states[id]
+    }
+
+    /// The underlying search over state ids.
+    pub fn search(&self) -> &Search<W> {
+        &self.search
+    }
+
+    /// The shortest path from the start to `state`, `None` if the search never got there.
+    pub fn shortest_path(&self, state: &S) -> Option<Path<S, W>> {
+        let path = self.search.shortest_path(self.id(state)?)?;
+        Some(path.map(|id| self.states[id].clone()))
+    }
+
+    /// The shortest path to the goal, if one was found.
+    pub fn goal_path(&self) -> Option<Path<S, W>> {
+        self.shortest_path(self.goal()?)
+    }
+}
+
+/// Cheapest way from `start` to a state satisfying `is_goal`. `successors` lists the states
+/// reachable from a state together with the cost of getting there.
+pub fn dijkstra<S, W, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> StateSearch<S, W>
+where
+    S: Clone + Eq + Hash,
+    W: Weight,
+    I: IntoIterator<Item = (S, W)>,
+{
+    astar(start, successors, is_goal, |_| W::default())
+}
+
+/// Like `dijkstra`, guided by a `heuristic` that never overestimates the cost left to a goal.
+pub fn astar<S, W, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    heuristic: impl Fn(&S) -> W,
+) -> StateSearch<S, W>
+where
+    S: Clone + Eq + Hash,
+    W: Weight,
+    I: IntoIterator<Item = (S, W)>,
+{
+    let mut found = StateSearch::new(start);
+    let mut queue = PriorityQueue::new();
+    queue.push(0, Reverse(heuristic(&found.states[0])));
+
+    while let Some((u, _)) = queue.pop() {
+        let state = found.states[u].clone();
+        if is_goal(&state) {
+            found.goal = Some(u);
+            break;
+        }
+        let u_dist = found
+            .search
+            .distance(u)
+            .expect("ERROR: Queued state without distance.");
+        for (next, cost) in successors(&state) {
+            let v = found.intern(next);
+            let alternative = u_dist + cost;
+            if found
+                .search
+                .distance(v)
+                .is_none_or(|v_dist| alternative < v_dist)
+            {
+                found.search.reach(v, alternative, Some(u));
+                queue.push_increase(v, Reverse(alternative + heuristic(&found.states[v])));
+            }
+        }
+    }
+    found
+}
+
+/// Fewest steps from `start` to a state satisfying `is_goal`, where `successors` lists the
+/// states one step away.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> StateSearch<S, u64>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut found = StateSearch::new(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(u) = queue.pop_front() {
+        let state = found.states[u].clone();
+        if is_goal(&state) {
+            found.goal = Some(u);
+            break;
+        }
+        let u_dist = found
+            .search
+            .distance(u)
+            .expect("ERROR: Queued state without distance.");
+        for next in successors(&state) {
+            let v = found.intern(next);
+            if !found.search.is_reached(v) {
+                found.search.reach(v, u_dist + 1, Some(u));
+                queue.push_back(v);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::graph::manhattan;
+
+    /// Two jugs of 3 and 5 litres, filled, emptied or poured into each other.
+    fn pour([a, b]: [u8; 2]) -> Vec<[u8; 2]> {
+        let a_to_b = a.min(5 - b);
+        let b_to_a = b.min(3 - a);
+        vec![
+            [3, b],
+            [a, 5],
+            [0, b],
+            [a, 0],
+            [a - a_to_b, b + a_to_b],
+            [a + b_to_a, b - b_to_a],
+        ]
+    }
+
+    #[test]
+    fn bfs_jugs() {
+        let found = bfs([0, 0], |&jugs| pour(jugs), |&[_, b]| b == 4);
+        assert_eq!(found.goal(), Some(&[3, 4]));
+        assert_eq!(found.cost(), Some(6));
+        let path = found.goal_path().unwrap();
+        assert_eq!(path.steps(), 6);
+        assert_eq!(path.cost(), 6);
+        assert_eq!((path.start(), path.end()), (&[0, 0], &[3, 4]));
+        assert_eq!(path.nodes()[1], [0, 5]);
+
+        let found = bfs([0, 0], |&jugs| pour(jugs), |&[a, b]| a + b == 9);
+        assert_eq!(found.goal(), None);
+        assert_eq!(found.cost(), None);
+        assert!(found.len() > 1);
+    }
+
+    #[test]
+    fn dijkstra_costs() {
+        // Count up to 10 from 1, adding one costs 1 and doubling costs 3.
+        let successors = |&n: &u32| {
+            [(n + 1, 1u32), (n * 2, 3)]
+                .into_iter()
+                .filter(|&(n, _)| n <= 10)
+        };
+        let found = dijkstra(1, successors, |&n| n == 10);
+        assert_eq!(found.cost(), Some(7));
+        assert_eq!(found.distance(&5), Some(4));
+        assert_eq!(found.distance(&11), None);
+        let path = found.goal_path().unwrap();
+        assert_eq!(path.nodes(), &[1, 2, 3, 4, 5, 10]);
+        assert_eq!(path.cost(), 7);
+
+        let found = dijkstra(1, successors, |&n| n == 1);
+        assert_eq!(found.cost(), Some(0));
+        assert_eq!(found.goal_path().unwrap().nodes(), &[1]);
+        assert_eq!(found.shortest_path(&11), None);
+    }
+
+    #[test]
+    fn astar_grid() {
+        // An open 20x20 grid, every step costs one.
+        let successors = |&[i, j]: &[usize; 2]| {
+            [
+                [i + 1, j],
+                [i, j + 1],
+                [i.wrapping_sub(1), j],
+                [i, j.wrapping_sub(1)],
+            ]
+            .into_iter()
+            .filter(|&[i, j]| i < 20 && j < 20)
+            .map(|idx| (idx, 1u64))
+        };
+        let goal = [19, 19];
+        let found = astar(
+            [0, 0],
+            successors,
+            |&idx| idx == goal,
+            |&idx| manhattan(idx, goal),
+        );
+        assert_eq!(found.cost(), Some(38));
+        let blind = dijkstra([0, 0], successors, |&idx| idx == goal);
+        assert_eq!(blind.cost(), Some(38));
+        assert!(found.len() <= blind.len());
+    }
+}
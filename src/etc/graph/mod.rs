@@ -0,0 +1,397 @@
+#![allow(unused)]
+
+mod distances;
+mod dot;
+pub mod implicit;
+mod search;
+
+pub use distances::Distances;
+pub use dot::Dot;
+pub use implicit::StateSearch;
+pub use search::{Path, Search};
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, Range},
+};
+
+use priority_queue::PriorityQueue;
+
+/// Dense index of a node, in the order the nodes were added.
+pub type NodeId = usize;
+
+/// Edge weights. The `Default` value is the cost of not moving at all.
+pub trait Weight: Copy + Ord + Add<Output = Self> + Default + Debug {}
+
+impl<W: Copy + Ord + Add<Output = W> + Default + Debug> Weight for W {}
+
+/// A directed graph with weighted edges. Nodes carry a label of any hashable type, e.g. a
+/// grid index or a valve name, and are numbered densely as they are added.
+#[derive(Debug, Clone)]
+pub struct Graph<L, W = u64> {
+    labels: Vec<L>,
+    ids: HashMap<L, NodeId>,
+    edges: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<L, W> Default for Graph<L, W> {
+    fn default() -> Self {
+        Graph {
+            labels: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<L: Clone + Eq + Hash, W: Weight> Graph<L, W> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node unless there already is one with this label. Returns its id either way.
+    pub fn add_node(&mut self, label: L) -> NodeId {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        self.edges.push(Vec::new());
+        id
+    }
+
+    /// Adds an edge from `from` to `to`. Undirected graphs add both directions.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: W) {
+        assert!(
+            to < self.len(),
+            "ERROR: Edge to unknown node {to}, there are {} nodes.",
+            self.len()
+        );
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn id(&self, label: &L) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: NodeId) -> &L {
+        &self.labels[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn node_ids(&self) -> Range<NodeId> {
+        0..self.len()
+    }
+
+    /// Outgoing edges of a node as `(neighbor, weight)`.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.edges[id]
+    }
+
+    /// Shortest distances from the nearest of `starts` to every node.
+    pub fn dijkstra(&self, starts: impl IntoIterator<Item = NodeId>) -> Search<W> {
+        self.best_first(starts, None, |_| W::default())
+    }
+
+    /// Shortest path from the nearest of `starts` to `goal`, guided by a `heuristic` that
+    /// never overestimates the distance left to the goal. The search stops at the goal, so
+    /// only nodes on the way have their final distance.
+    pub fn astar(
+        &self,
+        starts: impl IntoIterator<Item = NodeId>,
+        goal: NodeId,
+        heuristic: impl Fn(NodeId) -> W,
+    ) -> Search<W> {
+        self.best_first(starts, Some(goal), heuristic)
+    }
+
+    fn best_first(
+        &self,
+        starts: impl IntoIterator<Item = NodeId>,
+        goal: Option<NodeId>,
+        heuristic: impl Fn(NodeId) -> W,
+    ) -> Search<W> {
+        let mut search = Search::new(self.len(), starts);
+        let mut queue = PriorityQueue::new();
+        for &start in search.starts() {
+            queue.push(start, Reverse(heuristic(start)));
+        }
+
+        while let Some((u, _)) = queue.pop() {
+            if Some(u) == goal {
+                break;
+            }
+            let u_dist = search
+                .distance(u)
+                .expect("ERROR: Queued node without distance.");
+            for &(v, weight) in self.edges(u) {
+                let alternative = u_dist + weight;
+                if search.distance(v).is_none_or(|v_dist| alternative < v_dist) {
+                    search.reach(v, alternative, Some(u));
+                    queue.push_increase(v, Reverse(alternative + heuristic(v)));
+                }
+            }
+        }
+        search
+    }
+
+    /// Shortest distances counted in edges, ignoring the weights.
+    pub fn bfs(&self, starts: impl IntoIterator<Item = NodeId>) -> Search<u64> {
+        let mut search = Search::new(self.len(), starts);
+        let mut queue: VecDeque<NodeId> = search.starts().iter().copied().collect();
+
+        while let Some(u) = queue.pop_front() {
+            let u_dist = search
+                .distance(u)
+                .expect("ERROR: Queued node without distance.");
+            for &(v, _) in self.edges(u) {
+                if !search.is_reached(v) {
+                    search.reach(v, u_dist + 1, Some(u));
+                    queue.push_back(v);
+                }
+            }
+        }
+        search
+    }
+
+    /// Shortest distances in a graph whose edges weigh either nothing or all the same.
+    /// Free edges are followed first, so no priority queue is needed.
+    pub fn zero_one_bfs(&self, starts: impl IntoIterator<Item = NodeId>) -> Search<W> {
+        let mut search = Search::new(self.len(), starts);
+        let mut queue: VecDeque<NodeId> = search.starts().iter().copied().collect();
+
+        while let Some(u) = queue.pop_front() {
+            let u_dist = search
+                .distance(u)
+                .expect("ERROR: Queued node without distance.");
+            for &(v, weight) in self.edges(u) {
+                let alternative = u_dist + weight;
+                if search.distance(v).is_none_or(|v_dist| alternative < v_dist) {
+                    search.reach(v, alternative, Some(u));
+                    if weight == W::default() {
+                        queue.push_front(v);
+                    } else {
+                        queue.push_back(v);
+                    }
+                }
+            }
+        }
+        search
+    }
+
+    /// Shortest distances between all pairs of nodes with the Floyd–Warshall algorithm.
+    pub fn floyd_warshall(&self) -> Distances<W> {
+        let mut distances = Distances::new(self.len());
+        for u in self.node_ids() {
+            for &(v, weight) in self.edges(u) {
+                if distances.distance(u, v).is_none_or(|d| weight < d) {
+                    distances.set(u, v, weight);
+                }
+            }
+        }
+        for k in self.node_ids() {
+            for i in self.node_ids() {
+                let Some(ik) = distances.distance(i, k) else {
+                    continue;
+                };
+                for j in self.node_ids() {
+                    let Some(kj) = distances.distance(k, j) else {
+                        continue;
+                    };
+                    if distances.distance(i, j).is_none_or(|ij| ik + kj < ij) {
+                        distances.set(i, j, ik + kj);
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    /// Distances counted in edges between all pairs of nodes, one `bfs` per node. Cheaper
+    /// than `floyd_warshall` on sparse graphs.
+    pub fn all_pairs_bfs(&self) -> Distances<u64> {
+        let mut distances = Distances::new(self.len());
+        for u in self.node_ids() {
+            let search = self.bfs([u]);
+            for v in self.node_ids() {
+                if let Some(distance) = search.distance(v) {
+                    distances.set(u, v, distance);
+                }
+            }
+        }
+        distances
+    }
+
+    /// A graph over just the nodes in `keep`, in that order, with an edge between two of them
+    /// wherever one reaches the other. Edges weigh the shortest distance in this graph.
+    pub fn compress(&self, keep: impl IntoIterator<Item = NodeId>) -> Graph<L, W> {
+        let mut compressed = Graph::new();
+        let kept: Vec<(NodeId, NodeId)> = keep
+            .into_iter()
+            .map(|id| (id, compressed.add_node(self.label(id).clone())))
+            .collect();
+        for &(u, new_u) in &kept {
+            let search = self.dijkstra([u]);
+            for &(v, new_v) in &kept {
+                if let Some(distance) = search.distance(v).filter(|_| new_u != new_v) {
+                    compressed.add_edge(new_u, new_v, distance);
+                }
+            }
+        }
+        compressed
+    }
+}
+
+/// Manhattan distance between two `Matrix` indices, a heuristic for `astar` on grids where
+/// every step costs one.
+pub fn manhattan(a: [usize; 2], b: [usize; 2]) -> u64 {
+    (a[0].abs_diff(b[0]) + a[1].abs_diff(b[1])) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -1-> b -1-> c, plus a shortcut a -5-> c and a lonely d.
+    fn example() -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        let [a, b, c] = ["a", "b", "c"].map(|label| graph.add_node(label));
+        graph.add_node("d");
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, c, 1);
+        graph.add_edge(a, c, 5);
+        graph
+    }
+
+    #[test]
+    fn labels() {
+        let mut graph = example();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.id(&"c"), Some(2));
+        assert_eq!(graph.label(1), &"b");
+        assert_eq!(graph.add_node("b"), 1);
+        assert_eq!(graph.id(&"e"), None);
+        assert_eq!(graph.edges(0), &[(1, 1), (2, 5)]);
+    }
+
+    #[test]
+    fn dijkstra() {
+        let graph = example();
+        let search = graph.dijkstra([0]);
+        assert_eq!(search.distance(2), Some(2));
+        assert_eq!(search.parent(2), Some(1));
+        assert_eq!(search.distance(0), Some(0));
+        assert_eq!(search.distance(3), None);
+
+        // The graph is untouched and can be searched again from elsewhere.
+        let search = graph.dijkstra([1, 3]);
+        assert_eq!(search.distance(2), Some(1));
+        assert_eq!(search.distance(0), None);
+        assert_eq!(search.distance(3), Some(0));
+    }
+
+    #[test]
+    fn bfs() {
+        let graph = example();
+        let search = graph.bfs([0]);
+        // One edge straight to c beats two cheaper ones.
+        assert_eq!(search.distance(2), Some(1));
+        assert_eq!(search.parent(2), Some(0));
+        assert_eq!(search.distance(3), None);
+    }
+
+    #[test]
+    fn zero_one_bfs() {
+        // a -0-> b -0-> c -0-> d is free, a -1-> d is not.
+        let mut graph: Graph<char, u8> = Graph::new();
+        let [a, b, c, d] = ['a', 'b', 'c', 'd'].map(|label| graph.add_node(label));
+        graph.add_edge(a, d, 1);
+        graph.add_edge(a, b, 0);
+        graph.add_edge(b, c, 0);
+        graph.add_edge(c, d, 0);
+        let search = graph.zero_one_bfs([a]);
+        assert_eq!(search.distance(d), Some(0));
+        assert_eq!(search.parent(d), Some(c));
+        assert_eq!(search, graph.dijkstra([a]));
+    }
+
+    #[test]
+    fn all_pairs() {
+        let graph = example();
+        let distances = graph.floyd_warshall();
+        for u in graph.node_ids() {
+            let search = graph.dijkstra([u]);
+            for v in graph.node_ids() {
+                assert_eq!(distances.distance(u, v), search.distance(v));
+            }
+        }
+        assert_eq!(distances.distance(0, 2), Some(2));
+        assert_eq!(distances.distance(2, 0), None);
+
+        let steps = graph.all_pairs_bfs();
+        assert_eq!(steps.distance(0, 2), Some(1));
+        assert_eq!(steps.distance(3, 3), Some(0));
+        assert_eq!(steps.distance(3, 0), None);
+    }
+
+    #[test]
+    fn compress() {
+        let graph = example();
+        let ids = ["c", "a", "d"].map(|label| graph.id(&label).unwrap());
+        let compressed = graph.compress(ids);
+        assert_eq!(compressed.len(), 3);
+        assert_eq!(compressed.label(0), &"c");
+        assert_eq!(compressed.edges(0), &[]);
+        assert_eq!(compressed.edges(1), &[(0, 2)]);
+        assert_eq!(compressed.edges(2), &[]);
+    }
+
+    /// A 5x5 grid with a wall in column 2 that is open in the bottom row.
+    fn grid() -> Graph<[usize; 2]> {
+        let open = |[i, j]: [usize; 2]| j != 2 || i == 4;
+        let mut graph: Graph<[usize; 2]> = Graph::new();
+        for i in 0..5 {
+            for j in 0..5 {
+                graph.add_node([i, j]);
+            }
+        }
+        for u in graph.node_ids() {
+            let [i, j] = *graph.label(u);
+            for next in [
+                [i + 1, j],
+                [i, j + 1],
+                [i.wrapping_sub(1), j],
+                [i, j.wrapping_sub(1)],
+            ] {
+                if let Some(v) = graph.id(&next).filter(|_| open(next) && open([i, j])) {
+                    graph.add_edge(u, v, 1);
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn astar() {
+        let graph = grid();
+        let [start, goal] = [[0, 0], [0, 4]].map(|idx| graph.id(&idx).unwrap());
+        let search = graph.astar([start], goal, |id| manhattan(*graph.label(id), [0, 4]));
+        assert_eq!(search.distance(goal), Some(12));
+        assert_eq!(
+            search.distance(goal),
+            graph.dijkstra([start]).distance(goal)
+        );
+        assert_eq!(search.distance(goal), graph.bfs([start]).distance(goal));
+    }
+}
@@ -0,0 +1,145 @@
+use super::{NodeId, Weight};
+
+/// What a search found: the distance of every reached node and the node it was reached from.
+/// It refers to nodes by id and does not borrow the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Search<W> {
+    starts: Vec<NodeId>,
+    distance: Vec<Option<W>>,
+    parent: Vec<Option<NodeId>>,
+}
+
+impl<W: Weight> Search<W> {
+    /// A search over `len` nodes that has only reached its starts.
+    pub(super) fn new(len: usize, starts: impl IntoIterator<Item = NodeId>) -> Self {
+        let mut search = Search {
+            starts: Vec::new(),
+            distance: vec![None; len],
+            parent: vec![None; len],
+        };
+        for start in starts {
+            if search.distance[start].is_none() {
+                search.starts.push(start);
+                search.reach(start, W::default(), None);
+            }
+        }
+        search
+    }
+
+    /// Makes room for nodes discovered while searching.
+    pub(super) fn grow(&mut self, len: usize) {
+        self.distance.resize(len, None);
+        self.parent.resize(len, None);
+    }
+
+    pub(super) fn reach(&mut self, id: NodeId, distance: W, parent: Option<NodeId>) {
+        self.distance[id] = Some(distance);
+        self.parent[id] = parent;
+    }
+
+    pub fn starts(&self) -> &[NodeId] {
+        &self.starts
+    }
+
+    /// Distance from the nearest start, `None` if the node was not reached.
+    pub fn distance(&self, id: NodeId) -> Option<W> {
+        self.distance[id]
+    }
+
+    /// The node a shortest path arrives from. Starts and unreached nodes have none.
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.parent[id]
+    }
+
+    pub fn is_reached(&self, id: NodeId) -> bool {
+        self.distance[id].is_some()
+    }
+
+    /// The shortest path from a start to `destination`, `None` if it was not reached.
+    pub fn shortest_path(&self, destination: NodeId) -> Option<Path<NodeId, W>> {
+        let cost = self.distance(destination)?;
+        let mut nodes = vec![destination];
+        let mut current_node = destination;
+        while let Some(parent) = self.parent[current_node] {
+            nodes.push(parent);
+            current_node = parent;
+        }
+        nodes.reverse();
+        Some(Path { nodes, cost })
+    }
+}
+
+/// A path through a graph, listing every node from the start to the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, W> {
+    nodes: Vec<N>,
+    cost: W,
+}
+
+impl<N, W: Copy> Path<N, W> {
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn end(&self) -> &N {
+        &self.nodes[self.nodes.len() - 1]
+    }
+
+    /// Sum of the weights along the path.
+    pub fn cost(&self) -> W {
+        self.cost
+    }
+
+    /// Number of edges, one less than the number of nodes.
+    pub fn steps(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    /// The same path with its nodes replaced, e.g. by their labels.
+    pub fn map<M>(self, f: impl FnMut(N) -> M) -> Path<M, W> {
+        Path {
+            nodes: self.nodes.into_iter().map(f).collect(),
+            cost: self.cost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts() {
+        let search: Search<u64> = Search::new(4, [2, 0, 2]);
+        assert_eq!(search.starts(), &[2, 0]);
+        assert!(search.is_reached(0));
+        assert!(!search.is_reached(1));
+        assert_eq!(search.distance(2), Some(0));
+        assert_eq!(search.parent(2), None);
+    }
+
+    #[test]
+    fn shortest_path() {
+        let mut search: Search<u64> = Search::new(4, [0]);
+        search.reach(1, 3, Some(0));
+        search.reach(2, 4, Some(1));
+        let path = search.shortest_path(2).unwrap();
+        assert_eq!(path.nodes(), &[0, 1, 2]);
+        assert_eq!((path.start(), path.end()), (&0, &2));
+        assert_eq!(path.cost(), 4);
+        assert_eq!(path.steps(), 2);
+
+        // A start is a path of its own, an unreached node has none.
+        let path = search.shortest_path(0).unwrap();
+        assert_eq!(path.nodes(), &[0]);
+        assert_eq!((path.cost(), path.steps()), (0, 0));
+        assert_eq!(search.shortest_path(3), None);
+
+        let labels = path.map(|id| ["a", "b", "c", "d"][id]);
+        assert_eq!(labels.nodes(), &["a"]);
+    }
+}
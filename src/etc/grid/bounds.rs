@@ -0,0 +1,121 @@
+use super::direction::{Dir8, Direction, Offset};
+
+/// The size of a grid, for stepping between its cells without leaving it. With wrapping,
+/// steps off one edge come back in at the opposite one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    rows: usize,
+    cols: usize,
+    wrapping: bool,
+}
+
+impl Bounds {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Bounds {
+            rows,
+            cols,
+            wrapping: false,
+        }
+    }
+
+    /// The same bounds on a torus.
+    pub fn wrapping(self) -> Self {
+        Bounds {
+            wrapping: true,
+            ..self
+        }
+    }
+
+    pub fn contains(&self, [i, j]: [usize; 2]) -> bool {
+        i < self.rows && j < self.cols
+    }
+
+    /// The cell one step from `idx`, `None` if that leaves the grid.
+    pub fn step(&self, idx: [usize; 2], direction: impl Offset) -> Option<[usize; 2]> {
+        if !self.contains(idx) {
+            return None;
+        }
+        let [i, j] = idx;
+        let [di, dj] = direction.offset();
+        if self.wrapping {
+            let wrap = |x: usize, dx: isize, len: usize| {
+                (x as isize + dx).rem_euclid(len as isize) as usize
+            };
+            return Some([wrap(i, di, self.rows), wrap(j, dj, self.cols)]);
+        }
+        Some([i.checked_add_signed(di)?, j.checked_add_signed(dj)?])
+            .filter(|&idx| self.contains(idx))
+    }
+
+    /// The up to four cells next to `idx`, clockwise from the one above.
+    pub fn neighbors(self, idx: [usize; 2]) -> impl Iterator<Item = [usize; 2]> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(idx, direction))
+    }
+
+    /// The up to eight cells around `idx`, including diagonals.
+    pub fn neighbors8(self, idx: [usize; 2]) -> impl Iterator<Item = [usize; 2]> {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(idx, direction))
+    }
+
+    /// The cells seen looking from `idx` in `direction`, nearest first, up to the edge. With
+    /// wrapping the ray ends before it comes back around to `idx`.
+    pub fn ray(self, idx: [usize; 2], direction: impl Offset) -> impl Iterator<Item = [usize; 2]> {
+        std::iter::successors(self.step(idx, direction), move |&next| {
+            self.step(next, direction)
+        })
+        .take_while(move |&next| next != idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors() {
+        let bounds = Bounds::new(3, 4);
+        assert_eq!(
+            bounds.neighbors([1, 1]).collect::<Vec<_>>(),
+            [[0, 1], [1, 2], [2, 1], [1, 0]]
+        );
+        assert_eq!(
+            bounds.neighbors([0, 0]).collect::<Vec<_>>(),
+            [[0, 1], [1, 0]]
+        );
+        assert_eq!(bounds.neighbors8([0, 3]).count(), 3);
+        assert_eq!(bounds.neighbors8([1, 1]).count(), 8);
+        assert_eq!(bounds.neighbors([3, 0]).count(), 0);
+
+        let torus = bounds.wrapping();
+        assert_eq!(
+            torus.neighbors([0, 0]).collect::<Vec<_>>(),
+            [[2, 0], [0, 1], [1, 0], [0, 3]]
+        );
+        assert_eq!(torus.step([2, 3], Dir8::DownRight), Some([0, 0]));
+    }
+
+    #[test]
+    fn ray() {
+        let bounds = Bounds::new(3, 4);
+        assert_eq!(
+            bounds.ray([1, 1], Direction::Right).collect::<Vec<_>>(),
+            [[1, 2], [1, 3]]
+        );
+        assert_eq!(
+            bounds.ray([1, 1], Dir8::UpLeft).collect::<Vec<_>>(),
+            [[0, 0]]
+        );
+        assert_eq!(bounds.ray([0, 1], Direction::Up).count(), 0);
+        assert_eq!(
+            bounds
+                .wrapping()
+                .ray([1, 1], Direction::Left)
+                .collect::<Vec<_>>(),
+            [[1, 0], [1, 3], [1, 2]]
+        );
+    }
+}
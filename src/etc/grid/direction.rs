@@ -0,0 +1,117 @@
+/// Anything that moves an index by a fixed row and column offset.
+pub trait Offset: Copy {
+    /// Change of `[row, col]` for one step.
+    fn offset(self) -> [isize; 2];
+}
+
+/// The four directions along rows and columns. Up is towards row 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting at `Up`.
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl Offset for Direction {
+    fn offset(self) -> [isize; 2] {
+        match self {
+            Self::Up => [-1, 0],
+            Self::Right => [0, 1],
+            Self::Down => [1, 0],
+            Self::Left => [0, -1],
+        }
+    }
+}
+
+/// The eight directions including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// Clockwise, starting at `Up`.
+    pub const ALL: [Dir8; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl Offset for Dir8 {
+    fn offset(self) -> [isize; 2] {
+        match self {
+            Self::Up => [-1, 0],
+            Self::UpRight => [-1, 1],
+            Self::Right => [0, 1],
+            Self::DownRight => [1, 1],
+            Self::Down => [1, 0],
+            Self::DownLeft => [1, -1],
+            Self::Left => [0, -1],
+            Self::UpLeft => [-1, -1],
+        }
+    }
+}
+
+impl From<Direction> for Dir8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Self::Up,
+            Direction::Right => Self::Right,
+            Direction::Down => Self::Down,
+            Direction::Left => Self::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert_eq!(Dir8::UpLeft.opposite(), Dir8::DownRight);
+        for direction in Direction::ALL {
+            assert_eq!(direction.offset(), Dir8::from(direction).offset());
+            let [di, dj] = direction.offset();
+            assert_eq!(direction.opposite().offset(), [-di, -dj]);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+#![allow(unused)]
+
+mod bounds;
+mod direction;
+mod parse;
+pub mod sparse;
+mod view;
+
+pub use bounds::Bounds;
+pub use direction::{Dir8, Direction, Offset};
+pub use parse::{parse_grid, ParsedGrid};
+pub use sparse::{Point, SparseGrid};
+pub use view::{Cells, View};
+
+use std::{
+    fmt::{Display, Formatter, Result},
+    ops::{Deref, DerefMut, Index, IndexMut},
+};
+
+/// A `Matrix` whose size is only known at runtime, e.g. from the puzzle input. Cells are
+/// stored row by row on the heap.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `rows` rows of `cols` cells each, given row by row.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            rows * cols,
+            "ERROR: {} cells do not fill a grid of size ({rows},{cols}).",
+            cells.len()
+        );
+        Grid { rows, cols, cells }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn _index(&self, i: usize, j: usize) -> usize {
+        if i < self.rows && j < self.cols {
+            return i * self.cols + j;
+        }
+        panic!(
+            "ERROR: Index ({i},{j}) out of bounds for size ({},{}).",
+            self.rows, self.cols
+        )
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        if i < self.rows && j < self.cols {
+            return Some(&self.cells[self._index(i, j)]);
+        }
+        None
+    }
+
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut T> {
+        if i < self.rows && j < self.cols {
+            let idx = self._index(i, j);
+            return Some(&mut self.cells[idx]);
+        }
+        None
+    }
+
+    /// For stepping from cell to cell, e.g. `grid.bounds().neighbors(idx)`.
+    pub fn bounds(&self) -> Bounds {
+        Bounds::new(self.rows, self.cols)
+    }
+
+    pub fn each_index(&self) -> impl Iterator<Item = [usize; 2]> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |i| (0..cols).map(move |j| [i, j]))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, val: T) -> Self {
+        Grid {
+            rows,
+            cols,
+            cells: vec![val; rows * cols],
+        }
+    }
+}
+
+impl<T> Deref for Grid<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.cells
+    }
+}
+
+impl<T> DerefMut for Grid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cells
+    }
+}
+
+impl<T> Index<[usize; 2]> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: [usize; 2]) -> &Self::Output {
+        let [i, j] = index;
+        &self.cells[self._index(i, j)]
+    }
+}
+
+impl<T> IndexMut<[usize; 2]> for Grid<T> {
+    fn index_mut(&mut self, index: [usize; 2]) -> &mut Self::Output {
+        let [i, j] = index;
+        let idx = self._index(i, j);
+        &mut self.cells[idx]
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.cells[index]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                write!(f, "{}", self[[i, j]])?
+            }
+            writeln!(f)?
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index() {
+        let mut grid = Grid::new(2, 3, 0u8);
+        grid[[1, 2]] = 5;
+        grid[1] = 1;
+        assert_eq!(grid.rows(), 2);
+        assert_eq!(grid.cols(), 3);
+        assert_eq!(grid[5], 5);
+        assert_eq!(grid.get(0, 1), Some(&1));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        *grid.get_mut(1, 0).unwrap() = 7;
+        assert_eq!(&*grid, &[0, 1, 0, 7, 0, 5]);
+        assert_eq!(grid.to_string(), "010\n705\n");
+    }
+
+    #[test]
+    fn each_index() {
+        let grid = Grid::from_vec(2, 2, vec!['a', 'b', 'c', 'd']);
+        let indices: Vec<_> = grid.each_index().collect();
+        assert_eq!(indices, vec![[0, 0], [0, 1], [1, 0], [1, 1]]);
+        assert!(indices
+            .into_iter()
+            .map(|idx| grid[idx])
+            .eq(grid.iter().copied()));
+        assert_eq!(Grid::<u8>::new(0, 4, 0).each_index().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        let grid = Grid::new(2, 3, 0u8);
+        let _ = grid[[0, 3]];
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_size() {
+        Grid::from_vec(2, 3, vec![0; 5]);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use super::Grid;
+use crate::etc::ErasedError;
+
+/// A grid read from text, together with where its marker characters were found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedGrid<T> {
+    pub grid: Grid<T>,
+    markers: HashMap<char, Vec<[usize; 2]>>,
+}
+
+impl<T> ParsedGrid<T> {
+    /// Every position of `marker`, row by row. Empty if it was not asked for or not found.
+    pub fn markers(&self, marker: char) -> &[[usize; 2]] {
+        self.markers.get(&marker).map_or(&[], |found| found)
+    }
+
+    /// The position of a marker that has to occur exactly once, like a start or an end.
+    pub fn marker(&self, marker: char) -> Result<[usize; 2], ErasedError> {
+        match self.markers(marker) {
+            [idx] => Ok(*idx),
+            found => Err(format!(
+                "ERROR: Expected exactly one {marker:?} in the grid, found {}.",
+                found.len()
+            )
+            .into()),
+        }
+    }
+}
+
+/// Reads one cell per character with `cell`, which returns `None` for characters that are
+/// not allowed. All lines must be equally long; blank lines around the grid are ignored.
+/// The positions of the characters in `markers` are recorded, after they are read as cells
+/// like any other.
+pub fn parse_grid<T>(
+    s: &str,
+    markers: &[char],
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> Result<ParsedGrid<T>, ErasedError> {
+    let mut found: HashMap<char, Vec<[usize; 2]>> = HashMap::new();
+    let mut cells = Vec::new();
+    let mut rows = 0;
+    let mut cols = None;
+
+    let lines = s.lines().enumerate();
+    let lines = lines.skip_while(|(_, line)| line.trim().is_empty());
+    let mut lines: Vec<_> = lines.collect();
+    while lines.last().is_some_and(|(_, line)| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    for (i, line) in lines {
+        let mut len = 0;
+        for (j, c) in line.chars().enumerate() {
+            let value = cell(c).ok_or_else(|| {
+                format!(
+                    "ERROR: Unexpected {c:?} in line {}, column {}.",
+                    i + 1,
+                    j + 1
+                )
+            })?;
+            if markers.contains(&c) {
+                found.entry(c).or_default().push([rows, j]);
+            }
+            cells.push(value);
+            len += 1;
+        }
+        match cols {
+            None => cols = Some(len),
+            Some(cols) if cols != len => {
+                return Err(
+                    format!("ERROR: Line {} has {len} columns, expected {cols}.", i + 1).into(),
+                )
+            }
+            _ => {}
+        }
+        rows += 1;
+    }
+
+    Ok(ParsedGrid {
+        grid: Grid::from_vec(rows, cols.unwrap_or(0), cells),
+        markers: found,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digit(c: char) -> Option<u32> {
+        c.to_digit(10)
+    }
+
+    #[test]
+    fn parse() {
+        let parsed = parse_grid("\n12\n34\n\n", &[], digit).unwrap();
+        assert_eq!(parsed.grid, Grid::from_vec(2, 2, vec![1, 2, 3, 4]));
+
+        let parsed = parse_grid("S.#\n.#E\n..S", &['S', 'E', '@'], |c| {
+            matches!(c, '.' | '#' | 'S' | 'E').then_some(c == '#')
+        })
+        .unwrap();
+        assert_eq!(parsed.grid.iter().filter(|&&wall| wall).count(), 2);
+        assert_eq!(parsed.markers('S'), [[0, 0], [2, 2]]);
+        assert_eq!(parsed.marker('E').unwrap(), [1, 2]);
+        assert!(parsed.marker('S').is_err());
+        assert!(parsed.marker('@').is_err());
+
+        let empty = parse_grid("", &[], digit).unwrap();
+        assert!(empty.grid.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse_grid("12\n345", &[], digit).unwrap_err();
+        assert_eq!(err.to_string(), "ERROR: Line 2 has 3 columns, expected 2.");
+        let err = parse_grid("12\n3x", &[], digit).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ERROR: Unexpected 'x' in line 2, column 2."
+        );
+        let err = parse_grid("\n12\n3\n", &[], digit).unwrap_err();
+        assert_eq!(err.to_string(), "ERROR: Line 3 has 1 columns, expected 2.");
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Display, Formatter, Result, Write},
+    ops::Index,
+};
+
+use super::direction::{Dir8, Direction, Offset};
+
+/// A `[row, col]` position that may lie anywhere, including at negative coordinates.
+pub type Point = [i64; 2];
+
+/// The point one step from `point`.
+pub fn step(point: Point, direction: impl Offset) -> Point {
+    let [di, dj] = direction.offset();
+    [point[0] + di as i64, point[1] + dj as i64]
+}
+
+/// The four points next to `point`, clockwise from the one above.
+pub fn neighbors(point: Point) -> impl Iterator<Item = Point> {
+    Direction::ALL
+        .into_iter()
+        .map(move |direction| step(point, direction))
+}
+
+/// The eight points around `point`, including diagonals.
+pub fn neighbors8(point: Point) -> impl Iterator<Item = Point> {
+    Dir8::ALL
+        .into_iter()
+        .map(move |direction| step(point, direction))
+}
+
+/// A grid without bounds that only stores the cells that were set. It keeps track of the
+/// rows and columns in use, so it knows the box around its cells. Every access hashes, so
+/// a `Grid` is faster whenever the bounds are known up front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    /// Number of cells in each row and column that has any.
+    rows: BTreeMap<i64, usize>,
+    cols: BTreeMap<i64, usize>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            rows: BTreeMap::new(),
+            cols: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Sets a cell, returning what was there before.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        let old = self.cells.insert(point, value);
+        if old.is_none() {
+            let [i, j] = point;
+            *self.rows.entry(i).or_default() += 1;
+            *self.cols.entry(j).or_default() += 1;
+        }
+        old
+    }
+
+    /// Clears a cell, returning what was there.
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let old = self.cells.remove(&point)?;
+        let [i, j] = point;
+        for (lines, line) in [(&mut self.rows, i), (&mut self.cols, j)] {
+            let count = lines
+                .get_mut(&line)
+                .expect("ERROR: Cell in an unknown line.");
+            *count -= 1;
+            if *count == 0 {
+                lines.remove(&line);
+            }
+        }
+        Some(old)
+    }
+
+    /// The set cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The smallest and largest row and column of any set cell, as the corners
+    /// `[[min_row, min_col], [max_row, max_col]]`. `None` while the grid is empty.
+    pub fn bounding_box(&self) -> Option<[Point; 2]> {
+        let (&min_row, _) = self.rows.first_key_value()?;
+        let (&max_row, _) = self.rows.last_key_value()?;
+        let (&min_col, _) = self.cols.first_key_value()?;
+        let (&max_col, _) = self.cols.last_key_value()?;
+        Some([[min_row, min_col], [max_row, max_col]])
+    }
+
+    /// Number of cells inside the bounding box, set or not.
+    pub fn area(&self) -> u64 {
+        self.bounding_box().map_or(0, |[min, max]| {
+            (max[0] - min[0] + 1) as u64 * (max[1] - min[1] + 1) as u64
+        })
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("ERROR: No cell at {point:?}."))
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+impl<T: Display> SparseGrid<T> {
+    /// The cells from corner `min` to corner `max`, whatever the bounding box, like
+    /// `Display` does for the box. Frames printed through the same window all have the same
+    /// size.
+    pub fn to_string_in(&self, [min, max]: [Point; 2]) -> String {
+        let mut out = String::new();
+        for i in min[0]..=max[0] {
+            for j in min[1]..=max[1] {
+                match self.get([i, j]) {
+                    Some(value) => write!(out, "{value}").unwrap(),
+                    None => out.push('.'),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// The bounding box, top row first, with `.` for cells that are not set.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.bounding_box() {
+            Some(window) => f.write_str(&self.to_string_in(window)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounding_box() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounding_box(), None);
+        assert_eq!(grid.to_string(), "");
+
+        grid.insert([0, 0], 'a');
+        grid.insert([-2, 3], 'b');
+        grid.insert([1, -1], 'c');
+        assert_eq!(grid.insert([0, 0], 'd'), Some('a'));
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.bounding_box(), Some([[-2, -1], [1, 3]]));
+        assert_eq!(grid.area(), 20);
+        assert_eq!(grid.to_string(), "....b\n.....\n.d...\nc....\n");
+        assert_eq!(grid.to_string_in([[-1, -2], [0, 0]]), "...\n..d\n");
+
+        // The box shrinks again once the outermost cells are gone.
+        assert_eq!(grid.remove([-2, 3]), Some('b'));
+        assert_eq!(grid.remove([-2, 3]), None);
+        assert_eq!(grid.bounding_box(), Some([[0, -1], [1, 0]]));
+        assert_eq!(grid[[1, -1]], 'c');
+        assert!(!grid.contains([-2, 3]));
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(step([0, 0], Dir8::UpLeft), [-1, -1]);
+        assert_eq!(
+            neighbors([0, 0]).collect::<Vec<_>>(),
+            [[-1, 0], [0, 1], [1, 0], [0, -1]]
+        );
+        let grid: SparseGrid<()> = neighbors8([5, -5]).map(|point| (point, ())).collect();
+        assert_eq!(grid.len(), 8);
+        assert_eq!(grid.bounding_box(), Some([[4, -6], [6, -4]]));
+        assert!(!grid.contains([5, -5]));
+    }
+}
@@ -0,0 +1,324 @@
+use std::{
+    fmt::{Display, Formatter, Result},
+    ops::Index,
+};
+
+use super::Grid;
+use crate::etc::Matrix;
+
+/// Anything laid out in rows and columns of cells, readable through views that rearrange
+/// them without copying.
+pub trait Cells {
+    type Cell;
+
+    /// `[rows, cols]`.
+    fn size(&self) -> [usize; 2];
+
+    /// The cell at `[row, col]`, `None` outside.
+    fn cell(&self, idx: [usize; 2]) -> Option<&Self::Cell>;
+
+    fn rows(&self) -> usize {
+        self.size()[0]
+    }
+
+    fn cols(&self) -> usize {
+        self.size()[1]
+    }
+
+    fn each_index(&self) -> impl Iterator<Item = [usize; 2]> {
+        let [rows, cols] = self.size();
+        (0..rows).flat_map(move |i| (0..cols).map(move |j| [i, j]))
+    }
+
+    /// The cells of row `i` from left to right.
+    fn row(&self, i: usize) -> impl Iterator<Item = &Self::Cell> {
+        (0..self.cols()).map(move |j| self.cell([i, j]).expect("ERROR: Row out of bounds."))
+    }
+
+    /// The cells of column `j` from top to bottom.
+    fn col(&self, j: usize) -> impl Iterator<Item = &Self::Cell> {
+        (0..self.rows()).map(move |i| self.cell([i, j]).expect("ERROR: Column out of bounds."))
+    }
+
+    /// All cells as they are, to be rearranged further.
+    fn view(&self) -> View<'_, Self> {
+        let [rows, cols] = self.size();
+        View {
+            cells: self,
+            rows,
+            cols,
+            origin: [0, 0],
+            row_step: [1, 0],
+            col_step: [0, 1],
+        }
+    }
+
+    fn transposed(&self) -> View<'_, Self> {
+        self.view().transposed()
+    }
+
+    fn rotated_right(&self) -> View<'_, Self> {
+        self.view().rotated_right()
+    }
+
+    fn rotated_left(&self) -> View<'_, Self> {
+        self.view().rotated_left()
+    }
+
+    fn flipped_rows(&self) -> View<'_, Self> {
+        self.view().flipped_rows()
+    }
+
+    fn flipped_cols(&self) -> View<'_, Self> {
+        self.view().flipped_cols()
+    }
+
+    fn window(&self, top_left: [usize; 2], size: [usize; 2]) -> View<'_, Self> {
+        self.view().window(top_left, size)
+    }
+}
+
+/// Cells of a grid seen rearranged: rotated, flipped, transposed or cut down to a window.
+/// Stepping through the view steps through the grid along fixed directions, so any number
+/// of rearrangements cost nothing.
+#[derive(Debug)]
+pub struct View<'a, C: ?Sized> {
+    cells: &'a C,
+    rows: usize,
+    cols: usize,
+    /// Index in the grid of the view's `[0, 0]`.
+    origin: [usize; 2],
+    /// How the grid index changes when the view's row or column grows by one.
+    row_step: [isize; 2],
+    col_step: [isize; 2],
+}
+
+impl<C: ?Sized> Clone for View<'_, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: ?Sized> Copy for View<'_, C> {}
+
+impl<'a, C: Cells + ?Sized> View<'a, C> {
+    /// The index in the underlying grid of the view's `idx`.
+    pub fn source(&self, [i, j]: [usize; 2]) -> [usize; 2] {
+        assert!(
+            i < self.rows && j < self.cols,
+            "ERROR: Index ({i},{j}) out of bounds for size ({},{}).",
+            self.rows,
+            self.cols
+        );
+        let along = |axis: usize| {
+            self.origin[axis]
+                .checked_add_signed(
+                    i as isize * self.row_step[axis] + j as isize * self.col_step[axis],
+                )
+                .expect("ERROR: View reaches outside of its grid.")
+        };
+        [along(0), along(1)]
+    }
+
+    /// Rows become columns.
+    pub fn transposed(self) -> Self {
+        View {
+            rows: self.cols,
+            cols: self.rows,
+            row_step: self.col_step,
+            col_step: self.row_step,
+            ..self
+        }
+    }
+
+    /// Upside down.
+    pub fn flipped_rows(self) -> Self {
+        View {
+            origin: self.corner(self.rows, 0),
+            row_step: self.row_step.map(|d| -d),
+            ..self
+        }
+    }
+
+    /// Mirrored left to right.
+    pub fn flipped_cols(self) -> Self {
+        View {
+            origin: self.corner(0, self.cols),
+            col_step: self.col_step.map(|d| -d),
+            ..self
+        }
+    }
+
+    /// A quarter turn clockwise.
+    pub fn rotated_right(self) -> Self {
+        self.transposed().flipped_cols()
+    }
+
+    /// A quarter turn counterclockwise.
+    pub fn rotated_left(self) -> Self {
+        self.transposed().flipped_rows()
+    }
+
+    /// The `size` cells from `top_left` on.
+    pub fn window(self, top_left: [usize; 2], size: [usize; 2]) -> Self {
+        let [i, j] = top_left;
+        let [rows, cols] = size;
+        assert!(
+            i + rows <= self.rows && j + cols <= self.cols,
+            "ERROR: Window of size ({rows},{cols}) at ({i},{j}) does not fit into ({},{}).",
+            self.rows,
+            self.cols
+        );
+        if rows == 0 || cols == 0 {
+            return View { rows, cols, ..self };
+        }
+        View {
+            origin: self.source(top_left),
+            rows,
+            cols,
+            ..self
+        }
+    }
+
+    /// The source of the last cell before `rows` or `cols`, the origin of a flipped view.
+    fn corner(&self, rows: usize, cols: usize) -> [usize; 2] {
+        if self.rows == 0 || self.cols == 0 {
+            return self.origin;
+        }
+        self.source([rows.saturating_sub(1), cols.saturating_sub(1)])
+    }
+}
+
+impl<C: Cells + ?Sized> Cells for View<'_, C> {
+    type Cell = C::Cell;
+
+    fn size(&self) -> [usize; 2] {
+        [self.rows, self.cols]
+    }
+
+    fn cell(&self, [i, j]: [usize; 2]) -> Option<&Self::Cell> {
+        if i < self.rows && j < self.cols {
+            return self.cells.cell(self.source([i, j]));
+        }
+        None
+    }
+}
+
+impl<C: Cells + ?Sized> Index<[usize; 2]> for View<'_, C> {
+    type Output = C::Cell;
+
+    fn index(&self, idx: [usize; 2]) -> &Self::Output {
+        self.cells
+            .cell(self.source(idx))
+            .expect("ERROR: View reaches outside of its grid.")
+    }
+}
+
+impl<C: Cells + ?Sized> Display for View<'_, C>
+where
+    C::Cell: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for i in 0..self.rows {
+            for cell in self.row(i) {
+                write!(f, "{cell}")?
+            }
+            writeln!(f)?
+        }
+        Ok(())
+    }
+}
+
+impl<T> Cells for Grid<T> {
+    type Cell = T;
+
+    fn size(&self) -> [usize; 2] {
+        [Grid::rows(self), Grid::cols(self)]
+    }
+
+    fn cell(&self, [i, j]: [usize; 2]) -> Option<&T> {
+        self.get(i, j)
+    }
+}
+
+impl<const M: usize, const N: usize, T> Cells for Matrix<M, N, T>
+where
+    [T; M * N]:,
+{
+    type Cell = T;
+
+    fn size(&self) -> [usize; 2] {
+        [M, N]
+    }
+
+    fn cell(&self, [i, j]: [usize; 2]) -> Option<&T> {
+        self.get(i, j)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// abc
+    /// def
+    fn grid() -> Grid<char> {
+        Grid::from_vec(2, 3, "abcdef".chars().collect())
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let grid = grid();
+        assert_eq!(grid.row(1).collect::<String>(), "def");
+        assert_eq!(grid.col(2).collect::<String>(), "cf");
+        assert_eq!(grid.view().to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn rearranged() {
+        let grid = grid();
+        assert_eq!(grid.transposed().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.flipped_rows().to_string(), "def\nabc\n");
+        assert_eq!(grid.flipped_cols().to_string(), "cba\nfed\n");
+        assert_eq!(grid.rotated_right().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotated_left().to_string(), "cf\nbe\nad\n");
+        let around = grid
+            .view()
+            .rotated_right()
+            .rotated_right()
+            .rotated_left()
+            .rotated_left();
+        assert_eq!(around.to_string(), "abc\ndef\n");
+        assert_eq!(grid.rotated_right().source([0, 0]), [1, 0]);
+        assert_eq!(grid.rotated_right().cell([2, 1]), Some(&'c'));
+        assert_eq!(grid.rotated_right().cell([1, 2]), None);
+        assert_eq!(grid.flipped_rows()[[0, 1]], 'e');
+    }
+
+    #[test]
+    fn window() {
+        let grid = grid();
+        assert_eq!(grid.window([0, 1], [2, 2]).to_string(), "bc\nef\n");
+        assert_eq!(
+            grid.window([1, 0], [1, 3]).flipped_cols().to_string(),
+            "fed\n"
+        );
+        assert_eq!(
+            grid.rotated_left().window([1, 0], [2, 1]).to_string(),
+            "b\na\n"
+        );
+        assert_eq!(grid.window([2, 3], [0, 0]).to_string(), "");
+
+        let matrix: Matrix<2, 2, u8> = Matrix::new(1);
+        assert_eq!(
+            matrix.transposed().window([1, 0], [1, 2]).to_string(),
+            "11\n"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn window_too_large() {
+        grid().window([1, 1], [2, 2]);
+    }
+}
@@ -0,0 +1,277 @@
+#![allow(unused)]
+
+mod png;
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::grid::{Cells, Point, SparseGrid};
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// A picture of a grid, one pixel per cell until scaled up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, colour: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![colour; width * height],
+        }
+    }
+
+    /// Paints every cell of a `Grid`, `Matrix` or view in the colour `palette` picks for it.
+    pub fn from_cells<C: Cells + ?Sized>(cells: &C, palette: impl Fn(&C::Cell) -> Rgb) -> Self {
+        let [height, width] = cells.size();
+        let pixels = cells
+            .each_index()
+            .map(|idx| palette(cells.cell(idx).expect("ERROR: Cell out of bounds.")))
+            .collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Paints the bounding box of a `SparseGrid`. `palette` gets `None` for cells that are
+    /// not set.
+    pub fn from_sparse<T>(grid: &SparseGrid<T>, palette: impl Fn(Option<&T>) -> Rgb) -> Self {
+        match grid.bounding_box() {
+            Some(window) => Image::from_sparse_in(grid, window, palette),
+            None => Image::new(0, 0, BLACK),
+        }
+    }
+
+    /// Paints the cells from corner `min` to corner `max` of a `SparseGrid`, whatever its
+    /// bounding box. Frames of a simulation painted through the same window all have the same
+    /// size.
+    pub fn from_sparse_in<T>(
+        grid: &SparseGrid<T>,
+        [min, max]: [Point; 2],
+        palette: impl Fn(Option<&T>) -> Rgb,
+    ) -> Self {
+        assert!(
+            min[0] <= max[0] && min[1] <= max[1],
+            "ERROR: Window from {min:?} to {max:?} is empty."
+        );
+        let pixels = (min[0]..=max[0])
+            .flat_map(|i| (min[1]..=max[1]).map(move |j| [i, j]))
+            .map(|point| palette(grid.get(point)))
+            .collect();
+        Image {
+            width: (max[1] - min[1] + 1) as usize,
+            height: (max[0] - min[0] + 1) as usize,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The colour at column `x` of row `y`.
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        assert!(
+            x < self.width && y < self.height,
+            "ERROR: Pixel ({x},{y}) outside of a {}x{} image.",
+            self.width,
+            self.height
+        );
+        self.pixels[y * self.width + x]
+    }
+
+    /// Every pixel blown up to a `factor` by `factor` square, as single cells are hard to see.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x / factor, y / factor)))
+            .map(|(x, y)| self.pixel(x, y))
+            .collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Binary PPM (`P6`), which most image viewers open.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+        ppm
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        png::encode(self.width, self.height, &self.pixels)
+    }
+
+    /// Writes a `.png` or `.ppm` file, depending on the extension of `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("ERROR: Cannot tell the image format of {}.", path.display()),
+                ))
+            }
+        };
+        fs::write(path, bytes)
+    }
+}
+
+/// Numbered images of a running simulation, `<prefix>_0000.png`, `<prefix>_0001.png`, ... in
+/// one directory, e.g. for `ffmpeg -i sand_%04d.png sand.gif`.
+#[derive(Debug)]
+pub struct Frames {
+    dir: PathBuf,
+    prefix: String,
+    extension: &'static str,
+    count: usize,
+}
+
+impl Frames {
+    /// Frames as PNG files in `dir`, which is created if needed. Frames left there by an
+    /// earlier run with the same prefix are deleted, so they cannot end up in this sequence.
+    pub fn new(dir: impl Into<PathBuf>, prefix: &str) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let is_frame = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(prefix)?.strip_prefix('_'))
+                .and_then(|name| name.split_once('.'))
+                .is_some_and(|(number, extension)| {
+                    !number.is_empty()
+                        && number.bytes().all(|b| b.is_ascii_digit())
+                        && matches!(extension, "png" | "ppm")
+                });
+            if is_frame && path.is_file() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(Frames {
+            dir,
+            prefix: prefix.to_string(),
+            extension: "png",
+            count: 0,
+        })
+    }
+
+    /// Writes PPM files instead, which is faster but larger.
+    pub fn ppm(self) -> Self {
+        Frames {
+            extension: "ppm",
+            ..self
+        }
+    }
+
+    /// Number of frames written so far.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Saves the next frame, returning where it went.
+    pub fn push(&mut self, image: &Image) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!(
+            "{}_{:04}.{}",
+            self.prefix, self.count, self.extension
+        ));
+        image.save(&path)?;
+        self.count += 1;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::{grid::Cells, Grid};
+
+    #[test]
+    fn from_grids() {
+        let grid = Grid::from_vec(2, 3, vec![0u8, 1, 2, 3, 4, 5]);
+        let image = Image::from_cells(&grid.transposed(), |&v| [v, v * 10, 0]);
+        assert_eq!((image.width(), image.height()), (2, 3));
+        assert_eq!(image.pixel(1, 0), [3, 30, 0]);
+
+        let sparse: SparseGrid<()> = [([-1, 4], ()), ([1, 5], ())].into_iter().collect();
+        let image = Image::from_sparse(&sparse, |cell| cell.map_or(BLACK, |_| WHITE));
+        assert_eq!((image.width(), image.height()), (2, 3));
+        assert_eq!(image.pixel(0, 0), WHITE);
+        assert_eq!(image.pixel(1, 0), BLACK);
+        assert_eq!(image.pixel(1, 2), WHITE);
+
+        // A fixed window around the cells, with room to spare.
+        let image = Image::from_sparse_in(&sparse, [[-2, 3], [1, 6]], |cell| {
+            cell.map_or(BLACK, |_| WHITE)
+        });
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.pixel(1, 1), WHITE);
+        assert_eq!(image.pixel(2, 3), WHITE);
+        assert_eq!(image.pixel(0, 0), BLACK);
+
+        let image = Image::from_sparse(&sparse, |cell| cell.map_or(BLACK, |_| WHITE));
+        let scaled = image.scaled(3);
+        assert_eq!((scaled.width(), scaled.height()), (6, 9));
+        assert_eq!(scaled.pixel(2, 2), WHITE);
+        assert_eq!(scaled.pixel(3, 2), BLACK);
+    }
+
+    #[test]
+    fn ppm() {
+        let image = Image::new(2, 1, [1, 2, 3]);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\x01\x02\x03\x01\x02\x03");
+    }
+
+    #[test]
+    fn frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut frames = Frames::new(&dir, "test").unwrap().ppm();
+        frames.push(&Image::new(1, 1, BLACK)).unwrap();
+        let path = frames.push(&Image::new(1, 1, WHITE)).unwrap();
+        assert_eq!(path, dir.join("test_0001.ppm"));
+        assert_eq!(fs::read(&path).unwrap(), Image::new(1, 1, WHITE).to_ppm());
+        assert_eq!(frames.len(), 2);
+
+        // A new, shorter run replaces the frames of the old one, and leaves other files alone.
+        fs::write(dir.join("test_notes.txt"), "").unwrap();
+        let mut frames = Frames::new(&dir, "test").unwrap();
+        frames.push(&Image::new(1, 1, WHITE)).unwrap();
+        let mut files: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        files.sort();
+        assert_eq!(files, ["test_0000.png", "test_notes.txt"]);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(Image::new(1, 1, BLACK).save(dir.join("test.bmp")).is_err());
+    }
+}
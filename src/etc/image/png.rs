@@ -0,0 +1,133 @@
+//! Just enough of PNG to write 8-bit RGB images: the pixel data goes into a zlib stream of
+//! uncompressed deflate blocks, so no compressor is needed.
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+/// Largest payload of a stored deflate block.
+const MAX_BLOCK: usize = u16::MAX as usize;
+
+/// Encodes `height` rows of `width` pixels, given row by row.
+pub fn encode(width: usize, height: usize, pixels: &[[u8; 3]]) -> Vec<u8> {
+    assert_eq!(pixels.len(), width * height);
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8 bits per channel, RGB, deflate, no filters, not interlaced.
+    header.extend([8, 2, 0, 0, 0]);
+
+    // Each row starts with its filter type, 0 for none.
+    let mut raw = Vec::with_capacity(height * (1 + 3 * width));
+    if width > 0 {
+        for row in pixels.chunks(width) {
+            raw.push(0);
+            raw.extend(row.iter().flatten());
+        }
+    }
+
+    let mut png = SIGNATURE.to_vec();
+    chunk(&mut png, b"IHDR", &header);
+    chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// A zlib stream holding `data` in stored, i.e. uncompressed, deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32K window, no dictionary, check bits making the header divisible by 31.
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut n = 0;
+        while n < 256 {
+            let mut c = n as u32;
+            let mut k = 0;
+            while k < 8 {
+                c = if c & 1 == 1 {
+                    0xedb8_8320 ^ (c >> 1)
+                } else {
+                    c >> 1
+                };
+                k += 1;
+            }
+            table[n] = c;
+            n += 1;
+        }
+        table
+    };
+    !data.iter().fold(!0, |crc, &byte| {
+        TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (a, b) = data.iter().fold((1, 0), |(a, b), &byte| {
+        let a = (a + byte as u32) % MOD;
+        (a, (b + a) % MOD)
+    });
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(b""), 1);
+    }
+
+    #[test]
+    fn stored_blocks() {
+        let data = vec![7; MAX_BLOCK + 10];
+        let stream = zlib_stored(&data);
+        // Header, two block headers, the data and the checksum.
+        assert_eq!(stream.len(), 2 + 5 + MAX_BLOCK + 5 + 10 + 4);
+        assert_eq!(stream[2..7], [0, 0xff, 0xff, 0, 0]);
+        assert_eq!(
+            stream[7 + MAX_BLOCK..12 + MAX_BLOCK],
+            [1, 10, 0, 0xf5, 0xff]
+        );
+        assert_eq!(u16::from_be_bytes([stream[0], stream[1]]) % 31, 0);
+    }
+
+    #[test]
+    fn encode() {
+        let png = super::encode(2, 1, &[[255, 0, 0], [0, 0, 255]]);
+        assert_eq!(png[..8], SIGNATURE);
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(png[16..29], [0, 0, 0, 2, 0, 0, 0, 1, 8, 2, 0, 0, 0]);
+        assert_eq!(
+            png[png.len() - 12..],
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+    }
+}
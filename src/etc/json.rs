@@ -0,0 +1,286 @@
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult, Write},
+    iter::Peekable,
+    str::{Chars, FromStr},
+};
+
+use super::ErasedError;
+
+/// A JSON document. Numbers keep their text so no precision is lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    /// Members in the order they were written.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Quotes and escapes a string.
+pub fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => b.fmt(f),
+            Json::Number(n) => f.write_str(n),
+            Json::String(s) => f.write_str(&quote(s)),
+            Json::Array(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    let sep = if i == 0 { "" } else { ", " };
+                    write!(f, "{sep}{item}")?;
+                }
+                f.write_str("]")
+            }
+            Json::Object(members) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    let sep = if i == 0 { "" } else { ", " };
+                    write!(f, "{sep}{}: {value}", quote(key))?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+impl FromStr for Json {
+    type Err = ErasedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.chars().peekable(),
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.next() {
+            None => Ok(value),
+            Some(c) => Err(format!("ERROR: Unexpected {c:?} after JSON value.").into()),
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        self.chars.next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ErasedError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("ERROR: Expected {expected:?} in JSON, got {c:?}.").into()),
+            None => Err(format!("ERROR: Expected {expected:?}, JSON ended.").into()),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, ErasedError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') => self.string().map(Json::String),
+            Some('[') => {
+                self.next();
+                let mut items = Vec::new();
+                if !self.closes(']') {
+                    loop {
+                        items.push(self.value()?);
+                        if self.end_of(']')? {
+                            break;
+                        }
+                    }
+                }
+                Ok(Json::Array(items))
+            }
+            Some('{') => {
+                self.next();
+                let mut members = Vec::new();
+                if !self.closes('}') {
+                    loop {
+                        self.skip_whitespace();
+                        let key = self.string()?;
+                        self.expect(':')?;
+                        members.push((key, self.value()?));
+                        if self.end_of('}')? {
+                            break;
+                        }
+                    }
+                }
+                Ok(Json::Object(members))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let mut number = String::new();
+                while let Some(c) = self
+                    .peek()
+                    .filter(|c| "+-.eE".contains(*c) || c.is_ascii_digit())
+                {
+                    number.push(c);
+                    self.next();
+                }
+                if number.parse::<f64>().is_err() {
+                    return Err(format!("ERROR: Not a number in JSON: {number}").into());
+                }
+                Ok(Json::Number(number))
+            }
+            Some(_) => {
+                let mut word = String::new();
+                while let Some(c) = self.peek().filter(char::is_ascii_alphabetic) {
+                    word.push(c);
+                    self.next();
+                }
+                match word.as_str() {
+                    "null" => Ok(Json::Null),
+                    "true" => Ok(Json::Bool(true)),
+                    "false" => Ok(Json::Bool(false)),
+                    _ => Err(format!("ERROR: Unexpected {word:?} in JSON.").into()),
+                }
+            }
+            None => Err("ERROR: Expected a JSON value.".into()),
+        }
+    }
+
+    /// Eats `close` if it comes next, for empty arrays and objects.
+    fn closes(&mut self, close: char) -> bool {
+        self.skip_whitespace();
+        let closes = self.peek() == Some(close);
+        if closes {
+            self.next();
+        }
+        closes
+    }
+
+    /// After an item: whether the array or object ends here. Eats the comma otherwise.
+    fn end_of(&mut self, close: char) -> Result<bool, ErasedError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == close => Ok(true),
+            Some(',') => Ok(false),
+            _ => Err(format!("ERROR: Expected ',' or {close:?} in JSON.").into()),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ErasedError> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.next() {
+                None => return Err("ERROR: Unterminated string in JSON.".into()),
+                Some('"') => return Ok(out),
+                Some('\\') => match self.next() {
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('b') => out.push('\u{8}'),
+                    Some('f') => out.push('\u{c}'),
+                    Some(c @ ('"' | '\\' | '/')) => out.push(c),
+                    Some('u') => {
+                        let hex: String = (0..4).filter_map(|_| self.next()).collect();
+                        let c = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("ERROR: Bad escape \\u{hex} in JSON."))?;
+                        out.push(c);
+                    }
+                    c => return Err(format!("ERROR: Bad escape {c:?} in JSON.").into()),
+                },
+                Some(c) => out.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = r#"{"type": "List", "value": [1, -2.5e3, "a\nb", null, true, [], {}]}"#;
+        let json: Json = text.parse().unwrap();
+        assert_eq!(json.to_string(), text);
+        assert_eq!(json.get("type").and_then(Json::as_str), Some("List"));
+        assert!(matches!(json.get("value"), Some(Json::Array(items)) if items.len() == 7));
+    }
+
+    #[test]
+    fn whitespace_and_escapes() {
+        let json: Json = " { \"k\" :\n[ \"\\u0041\\/\" ] } ".parse().unwrap();
+        assert_eq!(
+            json,
+            Json::Object(vec![(
+                "k".into(),
+                Json::Array(vec![Json::String("A/".into())])
+            )])
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for text in [
+            "",
+            "[1,",
+            "[1 2]",
+            "{\"a\" 1}",
+            "\"open",
+            "nope",
+            "1 2",
+            "{1: 2}",
+        ] {
+            assert!(text.parse::<Json>().is_err(), "{text}");
+        }
+    }
+}
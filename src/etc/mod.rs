@@ -1,8 +1,12 @@
 pub mod graph;
+pub mod grid;
+pub mod image;
+pub mod json;
+pub mod ocr;
 pub mod solution;
 
 pub use graph::Graph;
-pub use graph::Node;
+pub use grid::Grid;
 pub use solution::ErasedError;
 pub use solution::Matrix;
 pub use solution::Solution;
@@ -0,0 +1,97 @@
+use super::{solution::Pixels, ErasedError};
+
+pub const LETTER_WIDTH: usize = 4;
+pub const LETTER_HEIGHT: usize = 6;
+/// Letters are followed by one dark column.
+const LETTER_STRIDE: usize = LETTER_WIDTH + 1;
+
+/// The letters of the 4x6 font puzzles draw with, as far as they are known.
+const FONT: [(char, [&str; LETTER_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Reads the capital letters drawn into a picture six pixels high.
+pub fn read(pixels: &Pixels) -> Result<String, ErasedError> {
+    if pixels.rows() != LETTER_HEIGHT {
+        return Err(format!(
+            "ERROR: Letters are {LETTER_HEIGHT} pixels high, the picture is {}.",
+            pixels.rows()
+        )
+        .into());
+    }
+    let cols = pixels.cols();
+    if cols % LETTER_STRIDE != LETTER_WIDTH && !cols.is_multiple_of(LETTER_STRIDE) {
+        return Err(format!("ERROR: A picture {cols} pixels wide is not a row of letters.").into());
+    }
+
+    let lit = |i, j| pixels.get(i, j) == Some(true);
+    (0..cols.div_ceil(LETTER_STRIDE))
+        .map(|idx| {
+            let col = idx * LETTER_STRIDE;
+            let is_letter = |rows: &[&str; LETTER_HEIGHT]| {
+                rows.iter().enumerate().all(|(i, row)| {
+                    row.chars()
+                        .enumerate()
+                        .all(|(j, c)| (c == '#') == lit(i, col + j))
+                })
+            };
+            FONT.iter()
+                .find(|(_, rows)| is_letter(rows))
+                .map(|&(letter, _)| letter)
+                .ok_or_else(|| format!("ERROR: Unknown letter at column {col}.").into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_letters() {
+        let pixels: Pixels = "\
+####..##..#....#..#.###..#....####...##.
+#....#..#.#....#..#.#..#.#....#.......#.
+###..#....#....####.###..#....###.....#.
+#....#.##.#....#..#.#..#.#....#.......#.
+#....#..#.#....#..#.#..#.#....#....#..#.
+####..###.####.#..#.###..####.#.....##.."
+            .parse()
+            .unwrap();
+        assert_eq!(read(&pixels).unwrap(), "EGLHBLFJ");
+    }
+
+    #[test]
+    fn read_whole_font() {
+        let rows: Vec<String> = (0..LETTER_HEIGHT)
+            .map(|i| FONT.map(|(_, rows)| rows[i]).join("."))
+            .collect();
+        let pixels: Pixels = rows.join("\n").parse().unwrap();
+        let letters: String = FONT.iter().map(|&(letter, _)| letter).collect();
+        assert_eq!(read(&pixels).unwrap(), letters);
+    }
+
+    #[test]
+    fn unreadable() {
+        assert!(read(&"####\n#...".parse().unwrap()).is_err());
+        assert!(read(&Pixels::new(6, 7)).is_err());
+        let stripes = "##..\n###.\n####\n#...\n##..\n###.".parse().unwrap();
+        assert!(read(&stripes).is_err());
+    }
+}
@@ -1,13 +1,21 @@
 #![allow(unused)]
 use std::{
+    convert::Infallible,
     error::Error,
     fmt::{Display, Formatter, Result},
+    hash::{Hash, Hasher},
     ops::{Index, IndexMut},
+    str::FromStr,
 };
 use Solution::*;
 
+use super::{grid::Bounds, json::Json};
+
 pub type ErasedError = Box<dyn Error + Send + Sync + 'static>;
 
+/// The answer to a part of a puzzle. Integer variants compare and hash by their value, so
+/// `U64(5) == I64(5)`, and a `F64` without a fractional part equals the same integer.
+#[derive(Debug, Clone)]
 pub enum Solution {
     I32(i32),
     I64(i64),
@@ -15,9 +23,185 @@ pub enum Solution {
     U32(u32),
     U64(u64),
     U128(u128),
+    F64(f64),
     Str(String),
+    /// Several values, written comma-separated.
+    List(Vec<Solution>),
+    /// Letters drawn in lit pixels.
+    Picture(Pixels),
+    /// A picture of letters together with the text read from it. Compares as the text.
+    Letters {
+        text: String,
+        picture: Pixels,
+    },
+    /// A part that has no solution yet.
+    Unsolved,
+}
+
+impl Solution {
+    /// Name of the variant holding the answer, e.g. `"U64"`.
+    pub fn variant(&self) -> &'static str {
+        match self {
+            I32(_) => "I32",
+            I64(_) => "I64",
+            I128(_) => "I128",
+            U32(_) => "U32",
+            U64(_) => "U64",
+            U128(_) => "U128",
+            F64(_) => "F64",
+            Str(_) => "Str",
+            List(_) => "List",
+            Picture(_) => "Picture",
+            Letters { .. } => "Letters",
+            Unsolved => "Unsolved",
+        }
+    }
+
+    /// Sign and magnitude of an integer answer, the same for every integer variant.
+    fn integer(&self) -> Option<(bool, u128)> {
+        match *self {
+            I32(x) => Some((x < 0, x.unsigned_abs() as u128)),
+            I64(x) => Some((x < 0, x.unsigned_abs() as u128)),
+            I128(x) => Some((x < 0, x.unsigned_abs())),
+            U32(x) => Some((false, x as u128)),
+            U64(x) => Some((false, x as u128)),
+            U128(x) => Some((false, x)),
+            F64(x) if x.fract() == 0.0 && x.abs() < 2f64.powi(128) => {
+                Some((x < 0.0, x.abs() as u128))
+            }
+            _ => None,
+        }
+    }
+
+    /// Serializes the answer as `{"type": "U64", "value": "5"}`. Unlike the text form this
+    /// keeps the variant. Lists hold an array of answers, `Unsolved` has a `null` value and
+    /// `Letters` add the `"picture"` they were read from.
+    pub fn to_json(&self) -> Json {
+        let value = match self {
+            List(items) => Json::Array(items.iter().map(Solution::to_json).collect()),
+            Unsolved => Json::Null,
+            _ => Json::String(self.to_string()),
+        };
+        let mut members = vec![
+            ("type".into(), Json::String(self.variant().into())),
+            ("value".into(), value),
+        ];
+        if let Letters { picture, .. } = self {
+            members.push(("picture".into(), Json::String(picture.to_string())));
+        }
+        Json::Object(members)
+    }
+
+    pub fn from_json(json: &Json) -> std::result::Result<Self, ErasedError> {
+        let variant = json
+            .get("type")
+            .and_then(Json::as_str)
+            .ok_or_else(|| format!("ERROR: Solution without a type: {json}"))?;
+        let value = json
+            .get("value")
+            .ok_or_else(|| format!("ERROR: Solution without a value: {json}"))?;
+        let invalid = || format!("ERROR: Not a valid {variant}: {value}");
+        let text = || value.as_str().ok_or_else(invalid);
+        Ok(match variant {
+            "I32" => I32(text()?.parse().map_err(|_| invalid())?),
+            "I64" => I64(text()?.parse().map_err(|_| invalid())?),
+            "I128" => I128(text()?.parse().map_err(|_| invalid())?),
+            "U32" => U32(text()?.parse().map_err(|_| invalid())?),
+            "U64" => U64(text()?.parse().map_err(|_| invalid())?),
+            "U128" => U128(text()?.parse().map_err(|_| invalid())?),
+            "F64" => F64(text()?.parse().map_err(|_| invalid())?),
+            "Str" => Str(text()?.into()),
+            "List" => match value {
+                Json::Array(items) => List(
+                    items
+                        .iter()
+                        .map(Solution::from_json)
+                        .collect::<std::result::Result<_, _>>()?,
+                ),
+                _ => return Err(invalid().into()),
+            },
+            "Picture" => Picture(text()?.parse()?),
+            "Letters" => Letters {
+                text: text()?.into(),
+                picture: json
+                    .get("picture")
+                    .and_then(Json::as_str)
+                    .ok_or_else(invalid)?
+                    .parse()?,
+            },
+            "Unsolved" => match value {
+                Json::Null => Unsolved,
+                _ => return Err(invalid().into()),
+            },
+            _ => return Err(format!("ERROR: Unknown solution type {variant:?}.").into()),
+        })
+    }
+}
+
+impl PartialEq for Solution {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Str(a) | Letters { text: a, .. }, Str(b) | Letters { text: b, .. }) => a == b,
+            (List(a), List(b)) => a == b,
+            (Picture(a), Picture(b)) => a == b,
+            (Unsolved, Unsolved) => true,
+            (F64(a), F64(b)) => a == b || a.is_nan() && b.is_nan(),
+            _ => self.integer().is_some() && self.integer() == other.integer(),
+        }
+    }
+}
+
+impl Eq for Solution {}
+
+impl Hash for Solution {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Str(s) | Letters { text: s, .. } => s.hash(state),
+            List(items) => items.hash(state),
+            Picture(pixels) => pixels.hash(state),
+            Unsolved => {}
+            F64(x) if self.integer().is_none() => {
+                let x = if x.is_nan() { f64::NAN } else { *x };
+                x.to_bits().hash(state)
+            }
+            _ => self.integer().hash(state),
+        }
+    }
+}
+
+/// Reads an answer back from its text form. Integers become the first of `I64`, `U64`,
+/// `I128` and `U128` that holds them, then come floats, `—` for `Unsolved` and pictures of
+/// `#` and `.`. Anything else, lists included, is a `Str`.
+impl FromStr for Solution {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        // Only canonical numbers, so that "007" or "+1" stay the text they are.
+        fn parse<T: FromStr + ToString>(s: &str) -> Option<T> {
+            s.parse().ok().filter(|x: &T| x.to_string() == s)
+        }
+        if s == UNSOLVED {
+            return Ok(Unsolved);
+        }
+        Ok(parse(s)
+            .map(I64)
+            .or_else(|| parse(s).map(U64))
+            .or_else(|| parse(s).map(I128))
+            .or_else(|| parse(s).map(U128))
+            .or_else(|| parse(s).filter(|x: &f64| x.is_finite()).map(F64))
+            .or_else(|| {
+                Some(s)
+                    .filter(|s| s.contains('\n'))?
+                    .parse()
+                    .ok()
+                    .map(Picture)
+            })
+            .unwrap_or_else(|| Str(s.into())))
+    }
 }
 
+const UNSOLVED: &str = "—";
+
 impl Display for Solution {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -27,11 +211,103 @@ impl Display for Solution {
             U32(x) => x.fmt(f),
             U64(x) => x.fmt(f),
             U128(x) => x.fmt(f),
-            Str(x) => x.fmt(f),
+            F64(x) => x.fmt(f),
+            Str(x) | Letters { text: x, .. } => x.fmt(f),
+            List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    let sep = if i == 0 { "" } else { "," };
+                    write!(f, "{sep}{item}")?;
+                }
+                Ok(())
+            }
+            Picture(pixels) => pixels.fmt(f),
+            Unsolved => f.write_str(UNSOLVED),
         }
     }
 }
 
+/// A picture of lit and dark pixels, indexed by row and column like `Matrix`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pixels {
+    rows: usize,
+    cols: usize,
+    lit: Vec<bool>,
+}
+
+impl Pixels {
+    /// A picture with all pixels dark.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Pixels {
+            rows,
+            cols,
+            lit: vec![false; rows * cols],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<bool> {
+        (i < self.rows && j < self.cols).then(|| self.lit[i * self.cols + j])
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, lit: bool) {
+        if i >= self.rows || j >= self.cols {
+            panic!(
+                "ERROR: Index ({i},{j}) out of bounds for size ({},{}).",
+                self.rows, self.cols
+            )
+        }
+        self.lit[i * self.cols + j] = lit;
+    }
+}
+
+/// Rows of `#` for lit and `.` for dark pixels.
+impl Display for Pixels {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for (i, row) in self.lit.chunks(self.cols.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for &lit in row {
+                f.write_str(if lit { "#" } else { "." })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses rows of `#` and `.`. Empty lines around the picture are ignored.
+impl FromStr for Pixels {
+    type Err = ErasedError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.trim_matches('\n').lines().collect();
+        let cols = lines.first().map_or(0, |line| line.len());
+        let mut pixels = Pixels::new(lines.len(), cols);
+        for (i, line) in lines.iter().enumerate() {
+            if line.len() != cols {
+                return Err(
+                    format!("ERROR: Row {} of the picture has a different width.", i + 1).into(),
+                );
+            }
+            for (j, c) in line.chars().enumerate() {
+                match c {
+                    '#' => pixels.set(i, j, true),
+                    '.' => {}
+                    _ => return Err(format!("ERROR: Unknown pixel {c:?}.").into()),
+                }
+            }
+        }
+        Ok(pixels)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Matrix<const M: usize, const N: usize, T>([T; M * N])
 where
@@ -62,6 +338,11 @@ where
         None
     }
 
+    /// For stepping from cell to cell, e.g. `matrix.bounds().neighbors(idx)`.
+    pub fn bounds(&self) -> Bounds {
+        Bounds::new(M, N)
+    }
+
     pub fn each_index(&self) -> impl Iterator<Item = [usize; 2]> {
         (0..M).flat_map(|i| (0..N).map(move |j| [i, j]))
     }
@@ -158,8 +439,130 @@ where
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashSet;
+
     use super::*;
 
+    #[test]
+    fn eq_across_integers() {
+        assert_eq!(U64(5), I64(5));
+        assert_eq!(I32(-3), I128(-3));
+        assert_eq!(U128(u128::MAX), U128(u128::MAX));
+        assert_ne!(I32(-5), U32(5));
+        assert_ne!(U64(5), Str("5".into()));
+        assert_eq!(Str("CMZ".into()), Str("CMZ".into()));
+
+        let set: HashSet<Solution> = [U32(7), I64(7), I128(-7), Str("7".into())].into();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn eq_other_variants() {
+        assert_eq!(F64(3.0), U32(3));
+        assert_ne!(F64(3.5), I64(3));
+        assert_eq!(F64(f64::NAN), F64(f64::NAN));
+        assert_eq!(
+            List(vec![U64(1), Str("a".into())]),
+            List(vec![I32(1), Str("a".into())])
+        );
+        assert_ne!(List(vec![U64(1)]), U64(1));
+        assert_eq!(Unsolved, Unsolved);
+        assert_ne!(Unsolved, U64(0));
+        let letters = Letters {
+            text: "EGLHBLFJ".into(),
+            picture: Pixels::new(6, 40),
+        };
+        assert_eq!(letters, Str("EGLHBLFJ".into()));
+        assert_eq!(letters.to_string(), "EGLHBLFJ");
+
+        let set: HashSet<Solution> = [F64(2.0), I64(2), F64(2.5), Unsolved, Unsolved].into();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn display_other_variants() {
+        assert_eq!(F64(2.5).to_string(), "2.5");
+        assert_eq!(
+            List(vec![U64(7), I32(-5), Str("x".into())]).to_string(),
+            "7,-5,x"
+        );
+        assert_eq!(Unsolved.to_string(), "—");
+
+        let mut pixels = Pixels::new(2, 3);
+        pixels.set(0, 0, true);
+        pixels.set(1, 2, true);
+        assert_eq!(pixels.get(1, 2), Some(true));
+        assert_eq!(pixels.get(2, 0), None);
+        assert_eq!(Picture(pixels).to_string(), "#..\n..#");
+    }
+
+    #[test]
+    fn parse_pixels() {
+        let pixels: Pixels = "\n#.\n.#\n".parse().unwrap();
+        assert_eq!((pixels.rows(), pixels.cols()), (2, 2));
+        assert_eq!(pixels.to_string(), "#.\n.#");
+        assert!("#.\n.".parse::<Pixels>().is_err());
+        assert!("#x".parse::<Pixels>().is_err());
+    }
+
+    #[test]
+    fn from_str() {
+        for solution in [I32(-12), U64(u64::MAX), I128(i128::MIN), U128(u128::MAX)] {
+            assert_eq!(solution.to_string().parse::<Solution>().unwrap(), solution);
+        }
+        assert!(matches!("42".parse(), Ok(I64(42))));
+        assert!(matches!("007".parse(), Ok(Str(s)) if s == "007"));
+        assert!(matches!("CMZ".parse(), Ok(Str(s)) if s == "CMZ"));
+        assert!(matches!("0.25".parse(), Ok(F64(x)) if x == 0.25));
+        assert!(matches!("—".parse(), Ok(Unsolved)));
+        assert!(matches!("#.\n.#".parse(), Ok(Picture(_))));
+        assert!(matches!("1,2".parse(), Ok(Str(_))));
+    }
+
+    #[test]
+    fn json() {
+        let picture: Pixels = "#.\n.#".parse().unwrap();
+        let letters = Letters {
+            text: "AB".into(),
+            picture: picture.clone(),
+        };
+        let list = List(vec![
+            U64(1),
+            List(vec![F64(0.5), Str("a,b".into())]),
+            Unsolved,
+        ]);
+        for solution in [
+            I32(-12),
+            U64(66719),
+            U128(u128::MAX),
+            F64(-1.25),
+            Str("#.\n\\\"".into()),
+            Picture(picture),
+            letters,
+            list,
+            Unsolved,
+        ] {
+            let json = solution.to_json();
+            let back = Solution::from_json(&json.to_string().parse().unwrap()).unwrap();
+            assert_eq!(back.variant(), solution.variant());
+            assert_eq!(back, solution);
+        }
+        assert_eq!(
+            U64(5).to_json().to_string(),
+            r#"{"type": "U64", "value": "5"}"#
+        );
+        assert!(
+            Solution::from_json(&r#"{"type": "U32", "value": "-1"}"#.parse().unwrap()).is_err()
+        );
+        assert!(Solution::from_json(&r#"{"type": "F16", "value": "1"}"#.parse().unwrap()).is_err());
+        assert!(
+            Solution::from_json(&r#"{"type": "List", "value": "1"}"#.parse().unwrap()).is_err()
+        );
+        assert!(
+            Solution::from_json(&r#"{"type": "Unsolved", "value": "0"}"#.parse().unwrap()).is_err()
+        );
+    }
+
     #[test]
     fn each_index() {
         const M: usize = 5;
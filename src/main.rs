@@ -1,85 +1,147 @@
 #![allow(incomplete_features)]
 #![feature(iter_array_chunks)]
-#![feature(array_windows)]
 #![feature(iter_advance_by)]
 #![feature(generic_const_exprs)]
-#![feature(get_many_mut)]
 
 mod days;
 mod etc;
+mod runner;
 
-use days::{
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
-};
 use etc::solution::Solution;
+use runner::{
+    bench::bench,
+    check::Answers,
+    isolate::{isolated, Abort},
+    parallel::map_ordered,
+    report::{Outcome, Record, Reporter},
+    visualize::visualize,
+    Options, Selection, USAGE,
+};
 
 use std::env;
-use std::time::Instant;
-
-pub type SolutionPair = (Solution, Solution);
+use std::io;
+use std::process;
+use std::time::{Duration, Instant};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        panic!("Please provide the day(s) to run as a command-line argument.");
+    let options = Options::from_args(env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{e}\n\n{USAGE}");
+        process::exit(2);
+    });
+
+    if options.list {
+        for day in days::DAYS {
+            let status = if day.is_implemented() {
+                ""
+            } else {
+                " (not implemented)"
+            };
+            println!("Day {:02}: {}{}", day.number, day.title, status);
+        }
+        return;
     }
 
-    let days: Vec<u8> = args[1..]
-        .iter()
-        .map(|x| {
-            x.parse()
-                .unwrap_or_else(|v| panic!("Not a valid day: {}", v))
-        })
-        .collect();
+    if let Some(day) = options.visualize {
+        if let Err(e) = visualize(day, &options.input, options.fps) {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        return;
+    }
 
-    let mut runtime = 0.0;
+    let answers = options.check.as_ref().map(|path| {
+        Answers::load(path).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(2);
+        })
+    });
 
-    for day in days {
-        let func = get_day_solver(day);
+    let mut reporter = Reporter::new(options.format, io::stdout()).expect("ERROR: stdout closed.");
+    let mut runtime = Duration::ZERO;
+    let (mut errors, mut mismatches) = (0, 0);
 
-        let time = Instant::now();
-        let (p1, p2) = func();
-        let elapsed_ms = time.elapsed().as_nanos() as f64 / 1_000_000.0;
+    let time = Instant::now();
+    map_ordered(
+        options.jobs,
+        &options.days,
+        |&selection| {
+            let mut record = run_day(selection, &options);
+            if let (Some(answers), Outcome::Solved { run, .. }) = (&answers, &record.outcome) {
+                let (part_1, part_2) = (run.part_1.as_ref(), run.part_2.as_ref());
+                record.check = Some(answers.check(selection.day, part_1, part_2));
+            }
+            record
+        },
+        |record| {
+            runtime += record.outcome.elapsed();
+            errors += record.is_error() as usize;
+            mismatches += record.is_mismatch() as usize;
+            reporter.record(&record).expect("ERROR: stdout closed.");
+        },
+    );
+    let wall_time = time.elapsed();
 
-        println!("\n=== Day {:02} ===", day);
-        println!("  · Part 1: {}", p1);
-        println!("  · Part 2: {}", p2);
-        println!("  · Elapsed: {:.4} ms", elapsed_ms);
+    reporter
+        .finish(runtime, wall_time)
+        .expect("ERROR: stdout closed.");
 
-        runtime += elapsed_ms;
+    if mismatches > 0 {
+        eprintln!("Check failed for {mismatches} day(s).");
+    }
+    if errors > 0 {
+        eprintln!("No answer for {errors} day(s).");
+    }
+    if errors + mismatches > 0 {
+        process::exit(1);
     }
-
-    println!("Total runtime: {:.4} ms", runtime);
 }
 
-fn get_day_solver(day: u8) -> fn() -> SolutionPair {
-    match day {
-        1 => day01::solve,
-        2 => day02::solve,
-        3 => day03::solve,
-        4 => day04::solve,
-        5 => day05::solve,
-        6 => day06::solve,
-        7 => day07::solve,
-        8 => day08::solve,
-        9 => day09::solve,
-        10 => day10::solve,
-        11 => day11::solve,
-        12 => day12::solve,
-        13 => day13::solve,
-        14 => day14::solve,
-        15 => day15::solve,
-        16 => day16::solve,
-        17 => day17::solve,
-        18 => day18::solve,
-        19 => day19::solve,
-        20 => day20::solve,
-        21 => day21::solve,
-        22 => day22::solve,
-        23 => day23::solve,
-        24 => day24::solve,
-        25 => day25::solve,
-        _ => unimplemented!(),
+fn run_day(selection: Selection, options: &Options) -> Record {
+    let Selection { day: number, parts } = selection;
+    let Some(day) = days::get(number) else {
+        return Record {
+            day: number,
+            title: "",
+            outcome: Outcome::Failed(format!("ERROR: There is no day {number}.")),
+            check: None,
+        };
+    };
+    let outcome = match day.run {
+        None => Outcome::NotImplemented,
+        Some(run) => match options.input.load(number) {
+            Err(e) => Outcome::Failed(e.to_string()),
+            Ok(input) => {
+                let (bench_mode, warmup, samples) =
+                    (options.bench, options.warmup, options.samples);
+                let name = format!("day{number:02}");
+                let solve = move || {
+                    if bench_mode {
+                        match bench(run, &input, parts, warmup, samples) {
+                            Ok((run, bench)) => Outcome::Solved {
+                                run,
+                                bench: Some(Box::new(bench)),
+                            },
+                            Err(e) => Outcome::Failed(e.to_string()),
+                        }
+                    } else {
+                        match run(&input, parts) {
+                            Ok(run) => Outcome::Solved { run, bench: None },
+                            Err(e) => Outcome::Failed(e.to_string()),
+                        }
+                    }
+                };
+                match isolated(name, options.timeout, solve) {
+                    Ok(outcome) => outcome,
+                    Err(Abort::Panicked(message)) => Outcome::Panicked(message),
+                    Err(Abort::TimedOut(limit)) => Outcome::TimedOut(limit),
+                }
+            }
+        },
+    };
+    Record {
+        day: number,
+        title: day.title,
+        outcome,
+        check: None,
     }
 }
@@ -0,0 +1,177 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    hint::black_box,
+    time::Duration,
+};
+
+use super::as_ms;
+use crate::{
+    days::{Parts, Run, RunFn},
+    etc::ErasedError,
+};
+
+/// Summary statistics over the samples of one phase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            secs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+            p95: percentile(&sorted, 95),
+        })
+    }
+}
+
+/// Nearest-rank percentile of already sorted samples.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Statistics of every phase of a day over all samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bench {
+    pub samples: usize,
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+    pub total: Stats,
+}
+
+/// Runs the selected parts of a day `warmup` times without measuring, then `samples` times. Returns the last run
+/// for its answers along with the statistics.
+pub fn bench(
+    run: RunFn,
+    input: &str,
+    parts: Parts,
+    warmup: usize,
+    samples: usize,
+) -> Result<(Run, Bench), ErasedError> {
+    if samples == 0 {
+        return Err("ERROR: Benchmarking needs at least one sample.".into());
+    }
+    for _ in 0..warmup {
+        black_box(run(black_box(input), parts)?);
+    }
+
+    let mut parse = Vec::with_capacity(samples);
+    let mut part_1 = Vec::with_capacity(samples);
+    let mut part_2 = Vec::with_capacity(samples);
+    let mut total = Vec::with_capacity(samples);
+    let mut last = None;
+    for _ in 0..samples {
+        let sample = black_box(run(black_box(input), parts)?);
+        parse.push(sample.parse_time);
+        part_1.push(sample.part_1_time);
+        part_2.push(sample.part_2_time);
+        total.push(sample.total_time());
+        last = Some(sample);
+    }
+
+    let stats = |samples: &[Duration]| Stats::from_samples(samples).expect("ERROR: No samples.");
+    let bench = Bench {
+        samples,
+        parse: stats(&parse),
+        part_1: stats(&part_1),
+        part_2: stats(&part_2),
+        total: stats(&total),
+    };
+    Ok((last.expect("ERROR: No samples."), bench))
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>10.4} {:>10.4} {:>10.4} {:>10.4} {:>10.4}",
+            as_ms(self.min),
+            as_ms(self.median),
+            as_ms(self.mean),
+            as_ms(self.std_dev),
+            as_ms(self.p95),
+        )
+    }
+}
+
+impl Display for Bench {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  · Samples: {}", self.samples)?;
+        writeln!(
+            f,
+            "    {:<10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "[ms]", "min", "median", "mean", "std dev", "p95"
+        )?;
+        writeln!(f, "    {:<10} {}", "Parse", self.parse)?;
+        writeln!(f, "    {:<10} {}", "Part 1", self.part_1)?;
+        writeln!(f, "    {:<10} {}", "Part 2", self.part_2)?;
+        write!(f, "    {:<10} {}", "Total", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(v: &[u64]) -> Vec<Duration> {
+        v.iter().map(|&x| Duration::from_millis(x)).collect()
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(&ms(&[4, 2, 8, 6])).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.p95, Duration::from_millis(8));
+        // Sample standard deviation of [2, 4, 6, 8] is sqrt(20 / 3).
+        let expected = (20.0f64 / 3.0).sqrt() / 1000.0;
+        assert!((stats.std_dev.as_secs_f64() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn stats_single_sample() {
+        let stats = Stats::from_samples(&ms(&[3])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let sorted = ms(&(1..=100).collect::<Vec<_>>());
+        assert_eq!(percentile(&sorted, 95), Duration::from_millis(95));
+        assert_eq!(percentile(&sorted[..10], 95), Duration::from_millis(10));
+        assert_eq!(percentile(&sorted[..1], 95), Duration::from_millis(1));
+    }
+}
@@ -0,0 +1,286 @@
+use std::{collections::HashMap, fs, path::Path, str::FromStr};
+
+use crate::{etc::ErasedError, Solution};
+
+/// Expected answers per day, read from a TOML file of the form
+///
+/// ```toml
+/// [day01]
+/// part_1 = 66719
+/// part_2 = "198551"
+/// ```
+///
+/// Only tables named `dayNN` with integer or string values are understood. Multi-line
+/// answers use `"""` strings. An empty table lists a day whose answers are still pending.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    days: HashMap<u8, [Option<String>; 2]>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, ErasedError> {
+        let s = fs::read_to_string(path)
+            .map_err(|e| format!("ERROR: Could not read {}: {e}", path.display()))?;
+        s.parse()
+            .map_err(|e| format!("{e} (in {})", path.display()).into())
+    }
+
+    pub fn get(&self, day: u8, part: usize) -> Option<&str> {
+        self.days.get(&day)?[part - 1].as_deref()
+    }
+
+    /// Compares both answers of a day against the expected ones. Parts that were not run are
+    /// left unchecked.
+    pub fn check(
+        &self,
+        day: u8,
+        part_1: Option<&Solution>,
+        part_2: Option<&Solution>,
+    ) -> [Verdict; 2] {
+        [
+            Verdict::new(part_1, self.get(day, 1)),
+            Verdict::new(part_2, self.get(day, 2)),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unchecked,
+}
+
+impl Verdict {
+    /// The expected text is read as a `Solution` and compared with `==`, so `U64(5)` passes
+    /// for `5` and letters pass for their text. Surrounding whitespace is ignored, so pictures
+    /// may start on a fresh line.
+    fn new(solution: Option<&Solution>, expected: Option<&str>) -> Self {
+        let (Some(solution), Some(expected)) = (solution, expected) else {
+            return Verdict::Unchecked;
+        };
+        let Ok(parsed) = expected.trim().parse::<Solution>();
+        if *solution == parsed {
+            Verdict::Pass
+        } else {
+            Verdict::Fail {
+                expected: expected.to_string(),
+            }
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::Unchecked => "unchecked",
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ErasedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+        let mut current_day = None;
+
+        let mut lines = s.lines().enumerate();
+        while let Some((idx, line)) = lines.next() {
+            let err = |msg: &str| format!("ERROR: Line {}: {msg}", idx + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                let (name, rest) = table
+                    .split_once(']')
+                    .ok_or_else(|| err("Unclosed table header."))?;
+                if !is_comment(rest) {
+                    return Err(err("Unexpected text after table header.").into());
+                }
+                let day = name
+                    .trim()
+                    .strip_prefix("day")
+                    .and_then(|n| n.parse::<u8>().ok())
+                    .ok_or_else(|| err("Tables must be named `dayNN`."))?;
+                answers.days.entry(day).or_default();
+                current_day = Some(day);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| err("Expected `key = value`."))?;
+            let part = match key.trim() {
+                "part_1" => 1,
+                "part_2" => 2,
+                key => return Err(err(&format!("Unknown key {key:?}.")).into()),
+            };
+            let day = current_day.ok_or_else(|| err("Answer outside of a [dayNN] table."))?;
+
+            let value = value.trim_start();
+            let (value, rest) = if let Some(value) = value.strip_prefix("\"\"\"") {
+                // Multi-line string, a newline right after the opening quotes is dropped.
+                let mut text = value.to_string();
+                loop {
+                    if let Some(end) = text.find("\"\"\"") {
+                        let rest = text.split_off(end + 3);
+                        text.truncate(end);
+                        break (unescape(text.strip_prefix('\n').unwrap_or(&text)), rest);
+                    }
+                    let (_, next) = lines
+                        .next()
+                        .ok_or_else(|| err("Unterminated multi-line string."))?;
+                    text.push('\n');
+                    text.push_str(next);
+                }
+            } else if let Some(value) = value.strip_prefix('"') {
+                let end = closing_quote(value).ok_or_else(|| err("Unterminated string."))?;
+                (unescape(&value[..end]), value[end + 1..].to_string())
+            } else {
+                let end = value.find('#').unwrap_or(value.len());
+                let number = value[..end].trim().replace('_', "");
+                if number.parse::<i128>().is_err() && number.parse::<u128>().is_err() {
+                    return Err(err("Expected an integer or a string.").into());
+                }
+                (Ok(number), String::new())
+            };
+            if !is_comment(&rest) {
+                return Err(err("Unexpected text after value.").into());
+            }
+
+            let slot = &mut answers.days.get_mut(&day).expect("ERROR: Table missing.")[part - 1];
+            let value = value.map_err(|e| err(&e.to_string()))?;
+            if slot.replace(value).is_some() {
+                return Err(err(&format!("part_{part} of day {day} given twice.")).into());
+            }
+        }
+        Ok(answers)
+    }
+}
+
+fn is_comment(s: &str) -> bool {
+    let s = s.trim();
+    s.is_empty() || s.starts_with('#')
+}
+
+/// Index of the first quote not escaped by a backslash.
+fn closing_quote(s: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(i),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn unescape(s: &str) -> Result<String, ErasedError> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            other => return Err(format!("Unknown escape sequence \\{other:?}.").into()),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+# Answers for my input
+[day01]
+part_1 = 66_719
+part_2 = "198551" # trailing comment
+
+[day05]
+part_1 = "PSNRGBTFT"
+
+[day10]
+part_2 = """
+#..#
+.##.
+"""
+
+[day16] # pending
+"#;
+
+    #[test]
+    fn parse() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(answers.get(1, 1), Some("66719"));
+        assert_eq!(answers.get(1, 2), Some("198551"));
+        assert_eq!(answers.get(5, 1), Some("PSNRGBTFT"));
+        assert_eq!(answers.get(5, 2), None);
+        assert_eq!(answers.get(10, 2), Some("#..#\n.##.\n"));
+        assert_eq!(answers.get(2, 1), None);
+        assert_eq!(answers.get(16, 1), None);
+    }
+
+    #[test]
+    fn parse_escapes() {
+        let answers: Answers = "[day10]\npart_2 = \"a\\n\\\"b\\\"\"".parse().unwrap();
+        assert_eq!(answers.get(10, 2), Some("a\n\"b\""));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = "[day01]\npart_1 = 1\npart_3 = 2"
+            .parse::<Answers>()
+            .unwrap_err();
+        assert!(err.to_string().starts_with("ERROR: Line 3:"));
+        assert!("part_1 = 1".parse::<Answers>().is_err());
+        assert!("[day01]\npart_1 = one".parse::<Answers>().is_err());
+        assert!("[day01]\npart_1 = \"one".parse::<Answers>().is_err());
+        assert!("[day01]\npart_1 = \"\"\"one".parse::<Answers>().is_err());
+        assert!("[day01]\npart_1 = 1\npart_1 = 1"
+            .parse::<Answers>()
+            .is_err());
+        assert!("[dayone]".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn check() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        let verdicts = answers.check(1, Some(&Solution::U64(66719)), Some(&Solution::I32(1)));
+        assert_eq!(verdicts[0], Verdict::Pass);
+        assert_eq!(
+            verdicts[1],
+            Verdict::Fail {
+                expected: "198551".into()
+            }
+        );
+
+        let picture = Solution::Picture(".##.\n#..#".parse().unwrap());
+        let verdicts = answers.check(10, Some(&Solution::I64(0)), Some(&picture));
+        assert_eq!(verdicts[1].name(), "fail");
+        let picture = Solution::Picture("#..#\n.##.".parse().unwrap());
+        let verdicts = answers.check(10, Some(&Solution::I64(0)), Some(&picture));
+        assert_eq!(verdicts, [Verdict::Unchecked, Verdict::Pass]);
+
+        // Text that merely prints the same is not the same answer.
+        let verdicts = answers.check(5, Some(&Solution::Str(" PSNRGBTFT".into())), None);
+        assert_eq!(verdicts[0].name(), "fail");
+        let verdicts = answers.check(1, Some(&Solution::F64(66719.0)), None);
+        assert_eq!(verdicts[0], Verdict::Pass);
+
+        let verdicts = answers.check(1, None, Some(&Solution::I32(198551)));
+        assert_eq!(verdicts, [Verdict::Unchecked, Verdict::Pass]);
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::etc::ErasedError;
+
+/// Where the puzzle input for a day is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// Inputs compiled into the binary.
+    #[cfg(feature = "baked-input")]
+    Baked,
+    /// A directory holding one `dayNN.txt` per day.
+    Dir(PathBuf),
+    /// A single file, used for whichever day is run.
+    File(PathBuf),
+}
+
+impl Default for InputSource {
+    #[cfg(feature = "baked-input")]
+    fn default() -> Self {
+        InputSource::Baked
+    }
+
+    #[cfg(not(feature = "baked-input"))]
+    fn default() -> Self {
+        InputSource::Dir("input".into())
+    }
+}
+
+impl InputSource {
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        if path.is_dir() {
+            InputSource::Dir(path)
+        } else {
+            InputSource::File(path)
+        }
+    }
+
+    pub fn load(&self, day: u8) -> Result<Cow<'static, str>, ErasedError> {
+        match self {
+            #[cfg(feature = "baked-input")]
+            InputSource::Baked => crate::days::baked_input(day)
+                .map(Cow::Borrowed)
+                .ok_or_else(|| format!("ERROR: No input baked in for day {day}.").into()),
+            InputSource::Dir(dir) => read_input(&dir.join(file_name(day))).map(Cow::Owned),
+            InputSource::File(file) => read_input(file).map(Cow::Owned),
+        }
+    }
+}
+
+fn file_name(day: u8) -> String {
+    format!("day{day:02}.txt")
+}
+
+fn read_input(path: &Path) -> Result<String, ErasedError> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("ERROR: Could not read {}: {e}", path.display()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name(7), "day07.txt");
+        assert_eq!(file_name(25), "day25.txt");
+    }
+
+    #[test]
+    fn load_from_dir() {
+        let source = InputSource::from_path("input");
+        assert!(matches!(source, InputSource::Dir(_)));
+        assert!(source.load(99).is_err());
+    }
+
+    #[test]
+    fn load_from_file() {
+        let source = InputSource::from_path("input/day01_test.txt");
+        assert_eq!(source, InputSource::File("input/day01_test.txt".into()));
+        let input = source.load(1).unwrap();
+        assert!(input.starts_with("1000"));
+    }
+}
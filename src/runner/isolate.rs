@@ -0,0 +1,112 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Once,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Why an isolated job did not produce a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Abort {
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+thread_local! {
+    /// Set on isolated threads, whose panics are reported by whoever waits for them.
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` on its own thread named `name`, so a panic only ends that thread. With a
+/// `timeout` the result is given up on after that long. The thread cannot be stopped, it is
+/// left running in the background until the process exits. Panics are not printed, their
+/// message comes back in `Abort::Panicked`.
+pub fn isolated<R>(
+    name: String,
+    timeout: Option<Duration>,
+    f: impl FnOnce() -> R + Send + 'static,
+) -> Result<R, Abort>
+where
+    R: Send + 'static,
+{
+    silence_isolated_panics();
+    let (tx, rx) = mpsc::channel();
+    thread::Builder::new()
+        .name(name)
+        .spawn(move || {
+            ISOLATED.set(true);
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            // Nobody is listening anymore after a timeout.
+            let _ = tx.send(result.map_err(panic_message));
+        })
+        .expect("ERROR: Could not spawn a thread.");
+
+    let result = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout).map_err(|e| match e {
+            RecvTimeoutError::Timeout => Abort::TimedOut(timeout),
+            RecvTimeoutError::Disconnected => Abort::Panicked("Thread vanished.".into()),
+        })?,
+        None => rx
+            .recv()
+            .map_err(|_| Abort::Panicked("Thread vanished.".into()))?,
+    };
+    result.map_err(Abort::Panicked)
+}
+
+/// Keeps the panic hook from printing for isolated threads, all other threads still get the
+/// hook that was there before.
+fn silence_isolated_panics() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !ISOLATED.get() {
+                previous(info)
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Panicked without a message.".into(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_value() {
+        assert_eq!(isolated("test".into(), None, || 6 * 7), Ok(42));
+    }
+
+    #[test]
+    fn catches_panics() {
+        let result: Result<(), _> = isolated("test".into(), None, || panic!("at day {}", 5));
+        assert_eq!(result, Err(Abort::Panicked("at day 5".into())));
+        let result: Result<(), _> = isolated("test".into(), None, || panic!("static"));
+        assert_eq!(result, Err(Abort::Panicked("static".into())));
+    }
+
+    #[test]
+    fn times_out() {
+        let timeout = Duration::from_millis(10);
+        let result = isolated("test".into(), Some(timeout), || {
+            thread::sleep(Duration::from_secs(1))
+        });
+        assert_eq!(result, Err(Abort::TimedOut(timeout)));
+        let result = isolated("test".into(), Some(Duration::from_secs(5)), || 1);
+        assert_eq!(result, Ok(1));
+    }
+}
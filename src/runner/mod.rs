@@ -0,0 +1,295 @@
+pub mod bench;
+pub mod check;
+pub mod input;
+pub mod isolate;
+pub mod parallel;
+pub mod report;
+pub mod select;
+pub mod visualize;
+
+pub use input::InputSource;
+pub use report::Format;
+pub use select::Selection;
+
+use std::{num::NonZeroU32, path::PathBuf, time::Duration};
+
+use crate::etc::ErasedError;
+
+pub const USAGE: &str = "\
+Usage: cargo run --release -- [--input PATH] [--format FORMAT] [--check] [--bench] [--jobs N] [--timeout SECS] DAYS...
+       cargo run --release -- --list
+       cargo run --release -- [--input PATH] [--fps N] --visualize DAY
+
+Days:
+  12             A single day.
+  1-13           A range of days.
+  12.2           A single part of a day.
+  all            Every day, `implemented` only the days with a solution.
+  ^17            Leaves a day, range or part out again, e.g. `all,^17`.
+                 Selections can be combined with commas or spaces: `1,5,9`.
+
+Options:
+  --list         List the registered days and whether they are implemented.
+  --input PATH   Read inputs from PATH. A directory is searched for dayNN.txt,
+                 a file is used as the input of the single selected day.
+                 Defaults to `input/`.
+  --format FORMAT
+                 Print results as `text`, `json` or `csv`. Defaults to `text`.
+  --check        Compare the answers against the answers file and exit with a
+                 non-zero status on any mismatch.
+  --answers PATH Answers file used by --check. Defaults to `answers.toml`.
+  --jobs N       Run up to N days at the same time. Defaults to 1.
+  --timeout SECS Give up on a day after SECS seconds, including all of its
+                 benchmark runs.
+  --bench        Run each day repeatedly and report statistics per phase.
+  --warmup N     Unmeasured runs before benchmarking. Defaults to 10.
+  --samples N    Measured runs when benchmarking. Defaults to 100.
+  --visualize DAY
+                 Play the simulation of a day in the terminal instead of
+                 solving it. Space pauses, n shows the next frame, q or
+                 Ctrl-C quits.
+  --fps N        Frames per second of --visualize. Defaults to 30.";
+
+/// Command-line options of the runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub days: Vec<Selection>,
+    pub input: InputSource,
+    pub list: bool,
+    pub format: Format,
+    pub check: Option<PathBuf>,
+    pub jobs: usize,
+    pub timeout: Option<Duration>,
+    pub bench: bool,
+    pub warmup: usize,
+    pub samples: usize,
+    pub visualize: Option<u8>,
+    pub fps: NonZeroU32,
+}
+
+impl Options {
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, ErasedError> {
+        let mut days = Vec::new();
+        let mut input = None;
+        let mut list = false;
+        let mut format = Format::default();
+        let mut check = false;
+        let mut answers = PathBuf::from("answers.toml");
+        let mut jobs = 1;
+        let mut timeout = None;
+        let mut bench = false;
+        let mut warmup = 10;
+        let mut samples = 100;
+        let mut visualize = None;
+        let mut fps = NonZeroU32::new(30).unwrap();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--list" => list = true,
+                "--format" => {
+                    format = args
+                        .next()
+                        .ok_or("ERROR: --format expects text, json or csv.")?
+                        .parse()?
+                }
+                "--check" => check = true,
+                "--answers" => {
+                    answers = args
+                        .next()
+                        .ok_or("ERROR: --answers expects a path.")?
+                        .into();
+                    check = true;
+                }
+                "--jobs" => jobs = parse_count(&arg, args.next())?,
+                "--timeout" => timeout = Some(parse_seconds(&arg, args.next())?),
+                "--bench" => bench = true,
+                "--warmup" => warmup = parse_count(&arg, args.next())?,
+                "--samples" => samples = parse_count(&arg, args.next())?,
+                "--visualize" => {
+                    let day = args.next().ok_or("ERROR: --visualize expects a day.")?;
+                    visualize =
+                        Some(day.parse().map_err(|_| {
+                            format!("ERROR: --visualize expects a day, got {day}.")
+                        })?);
+                }
+                "--fps" => fps = parse_fps(&arg, args.next())?,
+                "--input" => {
+                    let path = args.next().ok_or("ERROR: --input expects a path.")?;
+                    input = Some(InputSource::from_path(path));
+                }
+                _ if arg.starts_with("--") => {
+                    return Err(format!("ERROR: Unknown option {arg}").into())
+                }
+                _ => days.push(arg),
+            }
+        }
+        let days = select::parse_days(&days)?;
+
+        if visualize.is_some() && !days.is_empty() {
+            return Err("ERROR: --visualize shows a single day, given instead of DAYS.".into());
+        }
+        if days.is_empty() && !list && visualize.is_none() {
+            return Err("ERROR: Please provide the day(s) to run.".into());
+        }
+        if jobs == 0 {
+            return Err("ERROR: --jobs needs at least one job.".into());
+        }
+        if matches!(input, Some(InputSource::File(_))) && days.len() > 1 {
+            return Err("ERROR: An input file can only be used with a single day.".into());
+        }
+
+        Ok(Options {
+            days,
+            input: input.unwrap_or_default(),
+            list,
+            format,
+            check: check.then_some(answers),
+            jobs,
+            timeout,
+            bench,
+            warmup,
+            samples,
+            visualize,
+            fps,
+        })
+    }
+}
+
+fn parse_count(flag: &str, value: Option<String>) -> Result<usize, ErasedError> {
+    let value = value.ok_or_else(|| format!("ERROR: {flag} expects a number."))?;
+    value
+        .parse()
+        .map_err(|_| format!("ERROR: {flag} expects a number, got {value}.").into())
+}
+
+fn parse_fps(flag: &str, value: Option<String>) -> Result<NonZeroU32, ErasedError> {
+    let value = value.ok_or_else(|| format!("ERROR: {flag} expects a number of frames."))?;
+    value.parse().map_err(|_| {
+        format!(
+            "ERROR: {flag} expects between 1 and {} frames per second, got {value}.",
+            u32::MAX
+        )
+        .into()
+    })
+}
+
+fn parse_seconds(flag: &str, value: Option<String>) -> Result<Duration, ErasedError> {
+    let value = value.ok_or_else(|| format!("ERROR: {flag} expects a number of seconds."))?;
+    value
+        .parse()
+        .ok()
+        .filter(|&secs: &f64| secs > 0.0)
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| {
+            format!("ERROR: {flag} expects a positive number of seconds, got {value}.").into()
+        })
+}
+
+pub fn as_ms(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, ErasedError> {
+        Options::from_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn days() {
+        let options = parse(&["1", "12", "3"]).unwrap();
+        let days: Vec<u8> = options.days.iter().map(|s| s.day).collect();
+        assert_eq!(days, vec![1, 12, 3]);
+        assert_eq!(options.input, InputSource::default());
+    }
+
+    #[test]
+    fn input_dir() {
+        let options = parse(&["--input", "input", "5"]).unwrap();
+        assert_eq!(options.input, InputSource::Dir("input".into()));
+        assert_eq!(options.days, vec![Selection::new(5)]);
+    }
+
+    #[test]
+    fn input_file_single_day() {
+        assert!(parse(&["--input", "input/day01_test.txt", "1"]).is_ok());
+        assert!(parse(&["--input", "input/day01_test.txt", "1", "2"]).is_err());
+        assert!(parse(&["--input", "input/day01_test.txt", "1.2"]).is_ok());
+    }
+
+    #[test]
+    fn list() {
+        let options = parse(&["--list"]).unwrap();
+        assert!(options.list);
+        assert!(options.days.is_empty());
+    }
+
+    #[test]
+    fn format() {
+        let options = parse(&["--format", "csv", "10"]).unwrap();
+        assert_eq!(options.format, Format::Csv);
+        assert_eq!(parse(&["10"]).unwrap().format, Format::Text);
+        assert!(parse(&["--format", "xml", "10"]).is_err());
+    }
+
+    #[test]
+    fn check() {
+        assert_eq!(parse(&["1"]).unwrap().check, None);
+        let options = parse(&["--check", "1"]).unwrap();
+        assert_eq!(options.check, Some("answers.toml".into()));
+        let options = parse(&["--answers", "answers/alice.toml", "1"]).unwrap();
+        assert_eq!(options.check, Some("answers/alice.toml".into()));
+    }
+
+    #[test]
+    fn jobs() {
+        assert_eq!(parse(&["1"]).unwrap().jobs, 1);
+        assert_eq!(parse(&["--jobs", "4", "1", "2"]).unwrap().jobs, 4);
+        assert!(parse(&["--jobs", "0", "1"]).is_err());
+    }
+
+    #[test]
+    fn timeout() {
+        assert_eq!(parse(&["1"]).unwrap().timeout, None);
+        let options = parse(&["--timeout", "2.5", "1"]).unwrap();
+        assert_eq!(options.timeout, Some(Duration::from_millis(2500)));
+        assert!(parse(&["--timeout", "0", "1"]).is_err());
+        assert!(parse(&["--timeout", "soon", "1"]).is_err());
+    }
+
+    #[test]
+    fn bench() {
+        let options = parse(&["--bench", "--samples", "20", "6"]).unwrap();
+        assert!(options.bench);
+        assert_eq!(options.samples, 20);
+        assert_eq!(options.warmup, 10);
+        assert!(parse(&["--bench", "--warmup", "few", "6"]).is_err());
+    }
+
+    #[test]
+    fn visualize() {
+        assert_eq!(parse(&["1"]).unwrap().visualize, None);
+        let options = parse(&["--visualize", "14", "--fps", "12"]).unwrap();
+        assert_eq!(options.visualize, Some(14));
+        assert_eq!(options.fps.get(), 12);
+        assert!(options.days.is_empty());
+        assert_eq!(parse(&["--visualize", "9"]).unwrap().fps.get(), 30);
+        assert!(parse(&["--visualize", "9", "9"]).is_err());
+        assert!(parse(&["--visualize", "rope"]).is_err());
+        assert!(parse(&["--visualize", "9", "--fps", "0"]).is_err());
+        assert!(parse(&["--visualize", "9", "--fps", "4294967296"]).is_err());
+        assert!(parse(&["--visualize", "9", "--fps", "-3"]).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["one"]).is_err());
+        assert!(parse(&["1", "--input"]).is_err());
+        assert!(parse(&["--verbose", "1"]).is_err());
+        assert!(parse(&["1-26"]).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Applies `f` to every item on up to `jobs` worker threads. Results are handed to `emit`
+/// on the calling thread in the order of `items`, as soon as all earlier ones are done.
+pub fn map_ordered<T, R>(
+    jobs: usize,
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync,
+    mut emit: impl FnMut(R),
+) where
+    T: Sync,
+    R: Send,
+{
+    if jobs <= 1 {
+        items.iter().map(f).for_each(emit);
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                if tx.send((idx, f(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+        for (idx, result) in rx {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&expected) {
+                emit(result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        let items: Vec<u64> = (0..20).collect();
+        for jobs in [1, 3, 8, 50] {
+            let mut out = Vec::new();
+            map_ordered(
+                jobs,
+                &items,
+                |&i| {
+                    // Later items finish first.
+                    thread::sleep(Duration::from_millis(20 - i));
+                    i * i
+                },
+                |r| out.push(r),
+            );
+            assert_eq!(out, items.iter().map(|i| i * i).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn runs_concurrently() {
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+        map_ordered(
+            4,
+            &[(); 8],
+            |_| {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(20));
+                running.fetch_sub(1, Ordering::SeqCst);
+            },
+            |_| {},
+        );
+        assert!(max_running.load(Ordering::SeqCst) > 1);
+    }
+}
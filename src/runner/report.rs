@@ -0,0 +1,535 @@
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    str::FromStr,
+    time::Duration,
+};
+
+use super::{
+    as_ms,
+    bench::{Bench, Stats},
+    check::Verdict,
+};
+use crate::{
+    days::Run,
+    etc::{
+        json::{quote, Json},
+        ErasedError,
+    },
+    Solution,
+};
+
+/// Output format of the runner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = ErasedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("ERROR: Unknown format {s:?}, expected text, json or csv.").into()),
+        }
+    }
+}
+
+/// What running a single day produced.
+pub enum Outcome {
+    Solved {
+        run: Run,
+        bench: Option<Box<Bench>>,
+    },
+    NotImplemented,
+    Failed(String),
+    /// The day panicked, with the panic message.
+    Panicked(String),
+    /// The day was still running when the time limit ran out.
+    TimedOut(Duration),
+}
+
+impl Outcome {
+    fn status(&self) -> &'static str {
+        match self {
+            Outcome::Solved { .. } => "ok",
+            Outcome::NotImplemented => "not implemented",
+            Outcome::Failed(_) => "error",
+            Outcome::Panicked(_) => "failed",
+            Outcome::TimedOut(_) => "timeout",
+        }
+    }
+
+    /// Why the day did not produce answers, if it tried to.
+    fn error(&self) -> Option<String> {
+        match self {
+            Outcome::Failed(e) | Outcome::Panicked(e) => Some(e.clone()),
+            Outcome::TimedOut(limit) => Some(format!("Timed out after {} ms.", as_ms(*limit))),
+            Outcome::Solved { .. } | Outcome::NotImplemented => None,
+        }
+    }
+
+    /// Time the day took. For benchmarks this is the median.
+    pub fn elapsed(&self) -> Duration {
+        match self {
+            Outcome::Solved {
+                bench: Some(bench), ..
+            } => bench.total.median,
+            Outcome::Solved { run, .. } => run.total_time(),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+pub struct Record {
+    pub day: u8,
+    pub title: &'static str,
+    pub outcome: Outcome,
+    /// Verdicts of both parts when checking against known answers.
+    pub check: Option<[Verdict; 2]>,
+}
+
+impl Record {
+    /// Whether the day did not get to an answer, which fails any run.
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self.outcome,
+            Outcome::Failed(_) | Outcome::Panicked(_) | Outcome::TimedOut(_)
+        )
+    }
+
+    /// Whether an answer differs from the known one, which fails a `--check` run.
+    pub fn is_mismatch(&self) -> bool {
+        self.check
+            .iter()
+            .flatten()
+            .any(|v| matches!(v, Verdict::Fail { .. }))
+    }
+}
+
+const CSV_HEADER: &str = "day,title,status,part_1,part_1_type,part_2,part_2_type,\
+parse_ms,part_1_ms,part_2_ms,total_ms,error,part_1_check,part_2_check";
+
+/// Writes records as they come in, in the selected format.
+pub struct Reporter<W: Write> {
+    format: Format,
+    out: W,
+    records: usize,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, mut out: W) -> io::Result<Self> {
+        match format {
+            Format::Text => {}
+            Format::Json => write!(out, "[")?,
+            Format::Csv => writeln!(out, "{CSV_HEADER}")?,
+        }
+        Ok(Reporter {
+            format,
+            out,
+            records: 0,
+        })
+    }
+
+    pub fn record(&mut self, record: &Record) -> io::Result<()> {
+        match self.format {
+            Format::Text => write!(self.out, "{}", text(record))?,
+            Format::Json => {
+                let sep = if self.records == 0 { "" } else { "," };
+                write!(self.out, "{sep}\n  {}", json(record))?
+            }
+            Format::Csv => writeln!(self.out, "{}", csv(record))?,
+        }
+        self.records += 1;
+        self.out.flush()
+    }
+
+    /// `runtime` is the time summed over all days, `wall_time` the time the whole run took.
+    pub fn finish(mut self, runtime: Duration, wall_time: Duration) -> io::Result<()> {
+        match self.format {
+            Format::Text => {
+                writeln!(self.out, "Total runtime: {:.4} ms", as_ms(runtime))?;
+                writeln!(self.out, "Wall-clock time: {:.4} ms", as_ms(wall_time))
+            }
+            Format::Json => writeln!(self.out, "\n]"),
+            Format::Csv => Ok(()),
+        }
+    }
+}
+
+fn text(record: &Record) -> String {
+    let mut out = format!("\n=== Day {:02} ===\n", record.day);
+    match &record.outcome {
+        Outcome::Solved { run, bench } => {
+            let [check_1, check_2] = match &record.check {
+                Some([v1, v2]) => [text_verdict(v1), text_verdict(v2)],
+                None => Default::default(),
+            };
+            if let Some(part_1) = &run.part_1 {
+                writeln!(out, "  · Part 1: {}{check_1}", text_answer(part_1)).unwrap();
+            }
+            if let Some(part_2) = &run.part_2 {
+                writeln!(out, "  · Part 2: {}{check_2}", text_answer(part_2)).unwrap();
+            }
+            match bench {
+                Some(bench) => writeln!(out, "{bench}").unwrap(),
+                None => {
+                    writeln!(out, "  · Elapsed: {:.4} ms", as_ms(run.total_time())).unwrap();
+                    writeln!(out, "    · Parse:  {:.4} ms", as_ms(run.parse_time)).unwrap();
+                    if run.part_1.is_some() {
+                        writeln!(out, "    · Part 1: {:.4} ms", as_ms(run.part_1_time)).unwrap();
+                    }
+                    if run.part_2.is_some() {
+                        writeln!(out, "    · Part 2: {:.4} ms", as_ms(run.part_2_time)).unwrap();
+                    }
+                }
+            }
+        }
+        Outcome::NotImplemented => out.push_str("  · Not implemented\n"),
+        Outcome::Failed(e) => writeln!(out, "  · {e}").unwrap(),
+        Outcome::Panicked(e) => writeln!(out, "  · FAILED: {e}").unwrap(),
+        Outcome::TimedOut(limit) => {
+            writeln!(out, "  · TIMEOUT after {:.4} ms", as_ms(*limit)).unwrap()
+        }
+    }
+    out
+}
+
+/// Multi-line answers such as pictures start on a line of their own.
+fn text_answer(solution: &Solution) -> String {
+    let text = solution.to_string();
+    if text.contains('\n') && !text.starts_with('\n') {
+        format!("\n{text}")
+    } else {
+        text
+    }
+}
+
+fn text_verdict(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Pass => " (pass)".into(),
+        Verdict::Fail { expected } => format!(" (FAIL, expected {expected})"),
+        Verdict::Unchecked => " (no known answer)".into(),
+    }
+}
+
+fn json(record: &Record) -> String {
+    let mut out = format!(
+        "{{\"day\": {}, \"title\": {}, \"status\": {}",
+        record.day,
+        quote(record.title),
+        quote(record.outcome.status())
+    );
+    match &record.outcome {
+        Outcome::Solved { run, bench } => {
+            write!(
+                out,
+                ", \"part_1\": {}, \"part_2\": {}",
+                json_solution(run.part_1.as_ref()),
+                json_solution(run.part_2.as_ref())
+            )
+            .unwrap();
+            let [parse, part_1, part_2, total] = phase_times(run, bench.as_deref());
+            write!(
+                out,
+                ", \"time_ms\": {{\"parse\": {}, \"part_1\": {}, \"part_2\": {}, \"total\": {}}}",
+                as_ms(parse),
+                as_ms(part_1),
+                as_ms(part_2),
+                as_ms(total)
+            )
+            .unwrap();
+            if let Some(bench) = bench {
+                write!(
+                    out,
+                    ", \"bench\": {{\"samples\": {}, \"parse\": {}, \"part_1\": {}, \"part_2\": {}, \"total\": {}}}",
+                    bench.samples,
+                    json_stats(&bench.parse),
+                    json_stats(&bench.part_1),
+                    json_stats(&bench.part_2),
+                    json_stats(&bench.total)
+                )
+                .unwrap();
+            }
+        }
+        Outcome::NotImplemented => {}
+        outcome => {
+            let error = outcome.error().unwrap_or_default();
+            write!(out, ", \"error\": {}", quote(&error)).unwrap()
+        }
+    }
+    if let Some([v1, v2]) = &record.check {
+        write!(
+            out,
+            ", \"check\": {{\"part_1\": {}, \"part_2\": {}}}",
+            json_verdict(v1),
+            json_verdict(v2)
+        )
+        .unwrap();
+    }
+    out.push('}');
+    out
+}
+
+/// Times of parse, both parts and the total: the medians when benchmarking, otherwise those
+/// of the single run.
+fn phase_times(run: &Run, bench: Option<&Bench>) -> [Duration; 4] {
+    match bench {
+        Some(bench) => [
+            bench.parse.median,
+            bench.part_1.median,
+            bench.part_2.median,
+            bench.total.median,
+        ],
+        None => [
+            run.parse_time,
+            run.part_1_time,
+            run.part_2_time,
+            run.total_time(),
+        ],
+    }
+}
+
+/// Parts that were not run are `null`.
+fn json_solution(solution: Option<&Solution>) -> String {
+    solution.map_or(Json::Null, Solution::to_json).to_string()
+}
+
+fn json_verdict(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Fail { expected } => format!(
+            "{{\"verdict\": \"fail\", \"expected\": {}}}",
+            quote(expected)
+        ),
+        verdict => format!("{{\"verdict\": {}}}", quote(verdict.name())),
+    }
+}
+
+fn json_stats(stats: &Stats) -> String {
+    format!(
+        "{{\"min_ms\": {}, \"median_ms\": {}, \"mean_ms\": {}, \"std_dev_ms\": {}, \"p95_ms\": {}}}",
+        as_ms(stats.min),
+        as_ms(stats.median),
+        as_ms(stats.mean),
+        as_ms(stats.std_dev),
+        as_ms(stats.p95)
+    )
+}
+
+fn csv(record: &Record) -> String {
+    let mut fields = vec![
+        record.day.to_string(),
+        csv_field(record.title),
+        csv_field(record.outcome.status()),
+    ];
+    match &record.outcome {
+        Outcome::Solved { run, bench } => {
+            let [parse, part_1, part_2, total] = phase_times(run, bench.as_deref());
+            fields.extend([
+                csv_solution(run.part_1.as_ref()),
+                csv_variant(run.part_1.as_ref()),
+                csv_solution(run.part_2.as_ref()),
+                csv_variant(run.part_2.as_ref()),
+                as_ms(parse).to_string(),
+                as_ms(part_1).to_string(),
+                as_ms(part_2).to_string(),
+                as_ms(total).to_string(),
+                String::new(),
+            ]);
+        }
+        Outcome::NotImplemented => fields.extend(vec![String::new(); 9]),
+        outcome => {
+            fields.extend(vec![String::new(); 8]);
+            fields.push(csv_field(&outcome.error().unwrap_or_default()));
+        }
+    }
+    match &record.check {
+        Some([v1, v2]) => fields.extend([v1.name().to_string(), v2.name().to_string()]),
+        None => fields.extend([String::new(), String::new()]),
+    }
+    fields.join(",")
+}
+
+fn csv_solution(solution: Option<&Solution>) -> String {
+    solution.map_or_else(String::new, |s| csv_field(&s.to_string()))
+}
+
+fn csv_variant(solution: Option<&Solution>) -> String {
+    solution.map_or_else(String::new, |s| s.variant().to_string())
+}
+
+/// Quotes a field if it contains a separator, a quote or a line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solved() -> Record {
+        Record {
+            day: 10,
+            title: "Cathode-Ray Tube",
+            outcome: Outcome::Solved {
+                run: Run {
+                    part_1: Some(Solution::I64(13140)),
+                    part_2: Some(Solution::Str("\n#.\n.#".into())),
+                    parse_time: Duration::from_millis(1),
+                    part_1_time: Duration::from_millis(2),
+                    part_2_time: Duration::from_millis(3),
+                },
+                bench: None,
+            },
+            check: None,
+        }
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("csv".parse::<Format>().unwrap(), Format::Csv);
+        assert_eq!("text".parse::<Format>().unwrap(), Format::Text);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn escape_csv() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("\n#."), "\"\n#.\"");
+    }
+
+    #[test]
+    fn json_record() {
+        let json = json(&solved());
+        assert!(json.starts_with(r#"{"day": 10, "title": "Cathode-Ray Tube", "status": "ok""#));
+        assert!(json.contains(r#""part_1": {"type": "I64", "value": "13140"}"#));
+        assert!(json.contains(r#""part_2": {"type": "Str", "value": "\n#.\n.#"}"#));
+        assert!(json.contains(r#""total": 6}"#));
+    }
+
+    #[test]
+    fn csv_record() {
+        let line = csv(&solved());
+        assert_eq!(
+            line,
+            "10,Cathode-Ray Tube,ok,13140,I64,\"\n#.\n.#\",Str,1,2,3,6,,,"
+        );
+        let failed = Record {
+            day: 3,
+            title: "Rucksack Reorganization",
+            outcome: Outcome::Failed("ERROR: oops, bad".into()),
+            check: None,
+        };
+        assert_eq!(
+            csv(&failed),
+            "3,Rucksack Reorganization,error,,,,,,,,,\"ERROR: oops, bad\",,"
+        );
+    }
+
+    #[test]
+    fn single_part() {
+        let mut record = solved();
+        if let Outcome::Solved { run, .. } = &mut record.outcome {
+            run.part_1 = None;
+            run.part_1_time = Duration::ZERO;
+        }
+        assert!(!text(&record).contains("Part 1: "));
+        assert!(json(&record).contains(r#""part_1": null"#));
+        assert!(csv(&record).starts_with("10,Cathode-Ray Tube,ok,,,"));
+    }
+
+    #[test]
+    fn benched_record() {
+        let mut record = solved();
+        let stats = |ms| Stats::from_samples(&[Duration::from_millis(ms)]).unwrap();
+        if let Outcome::Solved { bench, .. } = &mut record.outcome {
+            *bench = Some(Box::new(Bench {
+                samples: 1,
+                parse: stats(10),
+                part_1: stats(20),
+                part_2: stats(30),
+                total: stats(60),
+            }));
+        }
+        // The medians, not the times of the last sample.
+        assert!(json(&record)
+            .contains(r#""time_ms": {"parse": 10, "part_1": 20, "part_2": 30, "total": 60}"#));
+        assert!(csv(&record).ends_with(",10,20,30,60,,,"));
+    }
+
+    #[test]
+    fn checked_record() {
+        let mut record = solved();
+        assert!(!record.is_mismatch());
+        record.check = Some([
+            Verdict::Pass,
+            Verdict::Fail {
+                expected: "ABC".into(),
+            },
+        ]);
+        assert!(record.is_mismatch() && !record.is_error());
+        assert!(text(&record).contains("  · Part 1: 13140 (pass)\n"));
+        assert!(json(&record).ends_with(
+            r#""check": {"part_1": {"verdict": "pass"}, "part_2": {"verdict": "fail", "expected": "ABC"}}}"#
+        ));
+        assert!(csv(&record).ends_with(",pass,fail"));
+    }
+
+    #[test]
+    fn aborted_record() {
+        let panicked = Record {
+            day: 5,
+            title: "Supply Stacks",
+            outcome: Outcome::Panicked("index out of bounds".into()),
+            check: None,
+        };
+        assert!(panicked.is_error() && !panicked.is_mismatch());
+        assert!(text(&panicked).contains("  · FAILED: index out of bounds\n"));
+        assert!(json(&panicked).ends_with(r#""status": "failed", "error": "index out of bounds"}"#));
+
+        let timed_out = Record {
+            outcome: Outcome::TimedOut(Duration::from_secs(2)),
+            ..panicked
+        };
+        assert!(timed_out.is_error());
+        assert!(text(&timed_out).contains("  · TIMEOUT after 2000.0000 ms\n"));
+        assert_eq!(
+            csv(&timed_out),
+            "5,Supply Stacks,timeout,,,,,,,,,Timed out after 2000 ms.,,"
+        );
+    }
+
+    #[test]
+    fn json_document() {
+        let mut out = Vec::new();
+        let mut reporter = Reporter::new(Format::Json, &mut out).unwrap();
+        reporter.record(&solved()).unwrap();
+        reporter
+            .record(&Record {
+                day: 15,
+                title: "Beacon Exclusion Zone",
+                outcome: Outcome::NotImplemented,
+                check: None,
+            })
+            .unwrap();
+        reporter.finish(Duration::ZERO, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("[\n  {\"day\": 10"));
+        assert!(out.ends_with(
+            "{\"day\": 15, \"title\": \"Beacon Exclusion Zone\", \"status\": \"not implemented\"}\n]\n"
+        ));
+    }
+}
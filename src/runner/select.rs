@@ -0,0 +1,190 @@
+use crate::{
+    days::{self, Parts, BOTH_PARTS},
+    etc::ErasedError,
+};
+
+/// A day picked on the command line together with the parts of it to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    pub day: u8,
+    pub parts: Parts,
+}
+
+impl Selection {
+    pub fn new(day: u8) -> Self {
+        Selection {
+            day,
+            parts: BOTH_PARTS,
+        }
+    }
+}
+
+/// Parses the day arguments. Every argument is a comma-separated list of terms, each one of
+///
+/// - `all`, or `implemented` for the days that have a solution,
+/// - a single day `12`, a range `1-13` or a single part `12.2`,
+/// - any of the above prefixed with `^` to drop it from the selection again.
+///
+/// Terms are applied from left to right and days keep the order they were first selected in.
+/// If there are only exclusions, they are taken from all days.
+pub fn parse_days(args: &[String]) -> Result<Vec<Selection>, ErasedError> {
+    let terms: Vec<&str> = args.iter().flat_map(|arg| arg.split(',')).collect();
+
+    let mut selected = Vec::new();
+    if !terms.is_empty() && terms.iter().all(|term| term.starts_with('^')) {
+        selected = parse_term("all")?;
+    }
+
+    for term in terms {
+        match term.strip_prefix('^') {
+            Some(term) => {
+                for excluded in parse_term(term)? {
+                    exclude(&mut selected, excluded);
+                }
+            }
+            None => {
+                for included in parse_term(term)? {
+                    include(&mut selected, included);
+                }
+            }
+        }
+    }
+
+    if selected.is_empty() && !args.is_empty() {
+        return Err(format!("ERROR: {} selects no days.", args.join(" ")).into());
+    }
+    Ok(selected)
+}
+
+fn include(selected: &mut Vec<Selection>, new: Selection) {
+    match selected.iter_mut().find(|s| s.day == new.day) {
+        Some(s) => s.parts = [s.parts[0] || new.parts[0], s.parts[1] || new.parts[1]],
+        None => selected.push(new),
+    }
+}
+
+fn exclude(selected: &mut Vec<Selection>, old: Selection) {
+    for s in selected.iter_mut().filter(|s| s.day == old.day) {
+        s.parts = [s.parts[0] && !old.parts[0], s.parts[1] && !old.parts[1]];
+    }
+    selected.retain(|s| s.parts != [false, false]);
+}
+
+fn parse_term(term: &str) -> Result<Vec<Selection>, ErasedError> {
+    match term {
+        "" => Err("ERROR: Empty day in selection.".into()),
+        "all" => Ok(days::DAYS
+            .iter()
+            .map(|d| Selection::new(d.number))
+            .collect()),
+        "implemented" => Ok(days::DAYS
+            .iter()
+            .filter(|d| d.is_implemented())
+            .map(|d| Selection::new(d.number))
+            .collect()),
+        _ => {
+            if let Some((first, last)) = term.split_once('-') {
+                let (first, last) = (parse_day(first, term)?, parse_day(last, term)?);
+                if first > last {
+                    return Err(format!("ERROR: Empty range of days: {term}").into());
+                }
+                Ok((first..=last).map(Selection::new).collect())
+            } else if let Some((day, part)) = term.split_once('.') {
+                let day = parse_day(day, term)?;
+                let parts = match part {
+                    "1" => [true, false],
+                    "2" => [false, true],
+                    _ => return Err(format!("ERROR: Not a valid part: {term}").into()),
+                };
+                Ok(vec![Selection { day, parts }])
+            } else {
+                Ok(vec![Selection::new(parse_day(term, term)?)])
+            }
+        }
+    }
+}
+
+fn parse_day(s: &str, term: &str) -> Result<u8, ErasedError> {
+    s.parse()
+        .ok()
+        .filter(|&day| days::get(day).is_some())
+        .ok_or_else(|| {
+            format!(
+                "ERROR: Not a valid day: {term}, expected a day from 1 to {}.",
+                days::DAYS.len()
+            )
+            .into()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Vec<Selection>, ErasedError> {
+        parse_days(&args.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+    }
+
+    fn days(args: &[&str]) -> Vec<u8> {
+        parse(args).unwrap().iter().map(|s| s.day).collect()
+    }
+
+    #[test]
+    fn single_days() {
+        assert_eq!(days(&["1", "12", "3"]), vec![1, 12, 3]);
+        assert_eq!(days(&["1,5,9"]), vec![1, 5, 9]);
+        assert_eq!(days(&["5", "5"]), vec![5]);
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(days(&["1-13"]), (1..=13).collect::<Vec<_>>());
+        assert_eq!(days(&["all"]), (1..=25).collect::<Vec<_>>());
+        assert_eq!(days(&["20-25,3"]), vec![20, 21, 22, 23, 24, 25, 3]);
+        assert!(days(&["implemented"]).contains(&1));
+        assert!(!days(&["implemented"]).contains(&25));
+    }
+
+    #[test]
+    fn exclusions() {
+        let expected: Vec<u8> = (1..=25).filter(|&d| d != 17).collect();
+        assert_eq!(days(&["all,^17"]), expected);
+        assert_eq!(days(&["all", "^17"]), expected);
+        assert_eq!(days(&["^17"]), expected);
+        assert_eq!(days(&["1-5,^2-4"]), vec![1, 5]);
+        assert_eq!(days(&["^2-4,3"]), vec![3]);
+    }
+
+    #[test]
+    fn parts() {
+        let selection = parse(&["12.2"]).unwrap();
+        assert_eq!(
+            selection,
+            vec![Selection {
+                day: 12,
+                parts: [false, true]
+            }]
+        );
+        assert_eq!(parse(&["12.1,12.2"]).unwrap(), vec![Selection::new(12)]);
+        let selection = parse(&["1-2,^2.1"]).unwrap();
+        assert_eq!(selection[1].parts, [false, true]);
+        assert_eq!(days(&["12,^12.1,^12.2,13"]), vec![13]);
+    }
+
+    #[test]
+    fn invalid() {
+        for args in [
+            &["one"][..],
+            &["0"],
+            &["26"],
+            &["1-26"],
+            &["5-3"],
+            &["12.3"],
+            &["12."],
+            &["1,,2"],
+            &["1,^1"],
+        ] {
+            assert!(parse(args).is_err(), "{args:?}");
+        }
+    }
+}
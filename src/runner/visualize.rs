@@ -0,0 +1,196 @@
+use std::{
+    io::{self, Read, Write},
+    num::NonZeroU32,
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::InputSource;
+use crate::{days, etc::ErasedError};
+
+/// Clears the terminal and moves the cursor to the top left.
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Keys understood while frames are playing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Stops or continues playing.
+    Pause,
+    /// Shows the next frame and stays paused.
+    Step,
+    Quit,
+}
+
+impl Control {
+    fn from_key(key: u8) -> Option<Self> {
+        match key {
+            b' ' | b'p' => Some(Control::Pause),
+            b'n' | b'.' => Some(Control::Step),
+            // Ctrl-C arrives as a key while the terminal does not turn it into a signal.
+            b'q' | 0x03 => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Plays the simulation of a day in the terminal at `fps` frames per second.
+pub fn visualize(number: u8, input: &InputSource, fps: NonZeroU32) -> Result<(), ErasedError> {
+    let day = days::get(number).ok_or_else(|| format!("ERROR: There is no day {number}."))?;
+    let frames = day
+        .frames
+        .ok_or_else(|| format!("ERROR: Day {number} has nothing to visualize."))?;
+    let frames = frames(&input.load(number)?)?;
+
+    let _keys = Keys::unbuffered();
+    let (tx, rx) = mpsc::channel();
+    // Blocks on stdin until the process exits, there is no way to stop it earlier.
+    thread::spawn(move || {
+        for key in io::stdin().lock().bytes() {
+            let Ok(key) = key else { break };
+            if let Some(control) = Control::from_key(key) {
+                if tx.send(control).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    let delay = Duration::from_secs(1) / fps.get();
+    play(frames, delay, &rx, io::stdout().lock())?;
+    Ok(())
+}
+
+/// Draws `frames` one after another, `delay` apart, following the `controls` that come in
+/// meanwhile. Once the controls are gone, the remaining frames just play. Returns the number
+/// of frames shown.
+pub fn play(
+    frames: impl Iterator<Item = String>,
+    delay: Duration,
+    controls: &Receiver<Control>,
+    mut out: impl Write,
+) -> io::Result<usize> {
+    let mut paused = false;
+    let mut listening = true;
+    let mut shown = 0;
+    for frame in frames {
+        shown += 1;
+        draw(&mut out, &frame, shown, paused)?;
+        let deadline = Instant::now() + delay;
+        loop {
+            let control = if !listening {
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                break;
+            } else if paused {
+                controls.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                controls.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            };
+            match control {
+                Ok(Control::Pause) => {
+                    paused = !paused;
+                    draw(&mut out, &frame, shown, paused)?;
+                }
+                Ok(Control::Step) => {
+                    paused = true;
+                    break;
+                }
+                Ok(Control::Quit) => return Ok(shown),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    listening = false;
+                    paused = false;
+                }
+            }
+        }
+    }
+    Ok(shown)
+}
+
+fn draw(out: &mut impl Write, frame: &str, number: usize, paused: bool) -> io::Result<()> {
+    write!(out, "{CLEAR}{frame}")?;
+    if !frame.ends_with('\n') {
+        writeln!(out)?
+    }
+    let state = if paused { " (paused)" } else { "" };
+    writeln!(
+        out,
+        "Frame {number}{state}   [space] pause   [n] next frame   [q] quit"
+    )?;
+    out.flush()
+}
+
+/// Hands key presses to the program right away instead of once per line and without echoing
+/// them, for as long as it lives. Ctrl-C comes in as a key rather than a signal, so quitting
+/// always passes through `Drop`, which puts the terminal back the way it was. Without `stty`,
+/// e.g. when stdin is not a terminal, nothing changes and lines still work.
+struct Keys {
+    /// The settings from before, as `stty -g` prints them.
+    saved: Option<String>,
+}
+
+impl Keys {
+    fn unbuffered() -> Self {
+        let saved = stty(&["-g"]).filter(|_| stty(&["-icanon", "-echo", "-isig"]).is_some());
+        Keys { saved }
+    }
+}
+
+impl Drop for Keys {
+    fn drop(&mut self) {
+        if let Some(saved) = &self.saved {
+            stty(&[saved.trim()]);
+        }
+    }
+}
+
+/// Runs `stty` on the terminal behind stdin, returning what it printed on success.
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(n: usize) -> impl Iterator<Item = String> {
+        (1..=n).map(|i| format!("#{i}"))
+    }
+
+    #[test]
+    fn controls() {
+        let (tx, rx) = mpsc::channel();
+        for control in [Control::Step, Control::Step, Control::Pause, Control::Quit] {
+            tx.send(control).unwrap();
+        }
+        // Without the controls this would take hours.
+        let mut out = Vec::new();
+        let shown = play(frames(10), Duration::from_secs(3600), &rx, &mut out).unwrap();
+        assert_eq!(shown, 3);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("#2\nFrame 2 (paused)"));
+        assert!(out.ends_with("#3\nFrame 3   [space] pause   [n] next frame   [q] quit\n"));
+    }
+
+    #[test]
+    fn without_controls() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(Control::Pause).unwrap();
+        drop(tx);
+        let mut out = Vec::new();
+        assert_eq!(play(frames(5), Duration::ZERO, &rx, &mut out).unwrap(), 5);
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches(CLEAR).count(), 6);
+        assert_eq!(Control::from_key(b'x'), None);
+        assert_eq!(Control::from_key(0x03), Some(Control::Quit));
+    }
+}